@@ -1,10 +1,16 @@
 use std::cmp::max;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 const DELETION_COST: usize = 2;
 const INSERTION_COST: usize = 2;
 // extra cost for starting a new group of changed tokens
 const INITIAL_MISMATCH_PENALTY: usize = 1;
+// Above this number of table cells the quadratic table is not filled; instead the alignment is
+// computed in linear space by `myers_operations`.
+const MAX_TABLE_SIZE: usize = 1 << 16;
+// Number of edit steps explored by the linear-space algorithm when searching for the middle snake
+// before settling for the furthest-reaching approximation.
+const MAX_MYERS_COST: usize = 1 << 10;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Operation {
@@ -30,6 +36,8 @@ pub struct Alignment<'a> {
     pub y: Vec<&'a str>,
     table: Vec<Cell>,
     dim: [usize; 2],
+    // Set instead of the table when the table would be too large (e.g. minified code).
+    linear_operations: Option<Vec<Operation>>,
 }
 
 impl<'a> Alignment<'a> {
@@ -38,6 +46,16 @@ impl<'a> Alignment<'a> {
         // TODO: Something downstream of the alignment algorithm requires that the first token in
         // both x and y is "", so this is explicitly inserted in `tokenize()`.
         let dim = [y.len() + 1, x.len() + 1];
+        if dim[0].saturating_mul(dim[1]) > MAX_TABLE_SIZE {
+            let linear_operations = Some(myers_operations(&x, &y));
+            return Self {
+                x,
+                y,
+                table: Vec::new(),
+                dim,
+                linear_operations,
+            };
+        }
         let table = vec![
             Cell {
                 parent: 0,
@@ -46,7 +64,13 @@ impl<'a> Alignment<'a> {
             };
             dim[0] * dim[1]
         ];
-        let mut alignment = Self { x, y, table, dim };
+        let mut alignment = Self {
+            x,
+            y,
+            table,
+            dim,
+            linear_operations: None,
+        };
         alignment.fill();
         alignment
     }
@@ -126,6 +150,9 @@ impl<'a> Alignment<'a> {
 
     /// Read edit operations from the table.
     pub fn operations(&self) -> Vec<Operation> {
+        if let Some(ops) = &self.linear_operations {
+            return ops.clone();
+        }
         let mut ops = VecDeque::with_capacity(max(self.x.len(), self.y.len()));
         let mut cell = &self.table[self.index(self.x.len(), self.y.len())];
         loop {
@@ -148,6 +175,140 @@ impl<'a> Alignment<'a> {
     }
 }
 
+/// Compute edit operations transforming x into y using Myers' O((N+M)D) diff algorithm with the
+/// linear-space divide-and-conquer refinement. Tokens are interned so that comparisons are cheap.
+/// As in the table-based alignment, runs of changes are emitted as deletions followed by
+/// insertions.
+fn myers_operations<'a>(x: &[&'a str], y: &[&'a str]) -> Vec<Operation> {
+    let mut interned: HashMap<&'a str, u32> = HashMap::new();
+    let mut intern = |tokens: &[&'a str]| -> Vec<u32> {
+        tokens
+            .iter()
+            .map(|&token| {
+                let next_id = interned.len() as u32;
+                *interned.entry(token).or_insert(next_id)
+            })
+            .collect()
+    };
+    let (a, b) = (intern(x), intern(y));
+    let mut ops = Vec::with_capacity(a.len() + b.len());
+    myers_diff(&a, &b, &mut ops);
+    group_changes(&mut ops);
+    ops
+}
+
+fn myers_diff(a: &[u32], b: &[u32], ops: &mut Vec<Operation>) {
+    let prefix = a.iter().zip(b).take_while(|(p, q)| p == q).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(p, q)| p == q)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+    ops.extend(std::iter::repeat_n(NoOp, prefix));
+    if a.is_empty() || b.is_empty() {
+        ops.extend(std::iter::repeat_n(Deletion, a.len()));
+        ops.extend(std::iter::repeat_n(Insertion, b.len()));
+    } else {
+        let (x_start, y_start, x_end, y_end) = middle_snake(a, b);
+        if (x_start, y_start) == (0, 0) && (x_end, y_end) == (0, 0)
+            || (x_start, y_start) == (a.len(), b.len())
+        {
+            // No progress is possible; should not happen, but avoid infinite recursion.
+            ops.extend(std::iter::repeat_n(Deletion, a.len()));
+            ops.extend(std::iter::repeat_n(Insertion, b.len()));
+        } else {
+            myers_diff(&a[..x_start], &b[..y_start], ops);
+            ops.extend(std::iter::repeat_n(NoOp, x_end - x_start));
+            myers_diff(&a[x_end..], &b[y_end..], ops);
+        }
+    }
+    ops.extend(std::iter::repeat_n(NoOp, suffix));
+}
+
+/// Find the middle snake of an optimal edit path between a and b, returned as (x_start, y_start,
+/// x_end, y_end). If the search becomes too expensive, return an empty snake at the point
+/// furthest along a forward path instead, giving a valid but possibly suboptimal split.
+fn middle_snake(a: &[u32], b: &[u32]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d + 1;
+    let mut forward = vec![0isize; 2 * offset as usize + 1];
+    let mut backward = vec![0isize; 2 * offset as usize + 1];
+    let index = |k: isize| (k + offset) as usize;
+    for d in 0..=max_d {
+        if d as usize > MAX_MYERS_COST {
+            let (x, y) = (-d + 1..d)
+                .step_by(2)
+                .filter(|k| (-m..=n).contains(k))
+                .map(|k| {
+                    let x = forward[index(k)].min(n).min(m + k);
+                    (x, x - k)
+                })
+                .max_by_key(|(x, y)| x + y)
+                .unwrap();
+            let (x, y) = (x as usize, y as usize);
+            return (x, y, x, y);
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[index(k - 1)] < forward[index(k + 1)]) {
+                forward[index(k + 1)]
+            } else {
+                forward[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x_start, y_start) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[index(k)] = x;
+            let k_backward = delta - k;
+            if odd
+                && (-(d - 1)..=d - 1).contains(&k_backward)
+                && x + backward[index(k_backward)] >= n
+            {
+                return (x_start as usize, y_start as usize, x as usize, y as usize);
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[index(k - 1)] < backward[index(k + 1)]) {
+                backward[index(k + 1)]
+            } else {
+                backward[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x_start, y_start) = (x, y);
+            while x < n && y < m && a[(n - 1 - x) as usize] == b[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[index(k)] = x;
+            let k_forward = delta - k;
+            if !odd && (-d..=d).contains(&k_forward) && x + forward[index(k_forward)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x_start) as usize,
+                    (m - y_start) as usize,
+                );
+            }
+        }
+    }
+    unreachable!("forward and backward paths always meet")
+}
+
+/// Reorder each run of changes so that its deletions precede its insertions.
+fn group_changes(ops: &mut [Operation]) {
+    for run in ops.split_mut(|op| *op == NoOp) {
+        run.sort_by_key(|op| *op == Insertion);
+    }
+}
+
 fn run_length_encode<T>(sequence: Vec<T>) -> Vec<(T, usize)>
 where
     T: Copy,
@@ -335,6 +496,42 @@ mod tests {
         .run();
     }
 
+    #[test]
+    fn test_myers_operations_long_lines() {
+        // Too large for the table: check that the operations are a valid alignment and that
+        // the unchanged tokens are found.
+        let before = "x = [".to_string() + &"1, 2, 3, ".repeat(200) + "];";
+        let after =
+            "x = [".to_string() + &"1, 2, 4, ".repeat(100) + &"1, 2, 3, ".repeat(100) + "];";
+        let (x, y) = (
+            before.graphemes(true).collect::<Vec<&str>>(),
+            after.graphemes(true).collect::<Vec<&str>>(),
+        );
+        let alignment = Alignment::new(x, y);
+        assert!(alignment.table.is_empty());
+        let ops = alignment.operations();
+        let count = |op| ops.iter().filter(|o| **o == op).count();
+        assert_eq!(count(NoOp) + count(Deletion), before.len());
+        assert_eq!(count(NoOp) + count(Insertion), after.len());
+        assert_eq!(count(Deletion), 100);
+        assert_eq!(count(Insertion), 100);
+    }
+
+    #[test]
+    fn test_myers_operations_bounded_cost() {
+        // Completely different lines: the cost limit is reached but the result is still valid.
+        let before = "ab".repeat(2000);
+        let after = "cd".repeat(2000);
+        let (x, y) = (
+            before.graphemes(true).collect::<Vec<&str>>(),
+            after.graphemes(true).collect::<Vec<&str>>(),
+        );
+        let ops = myers_operations(&x, &y);
+        assert_eq!(ops.len(), 8000);
+        assert!(ops[..4000].iter().all(|op| *op == Deletion));
+        assert!(ops[4000..].iter().all(|op| *op == Insertion));
+    }
+
     struct TestCase<'a> {
         before: &'a str,
         after: &'a str,
@@ -347,6 +544,7 @@ mod tests {
         pub fn run(&self) {
             self.assert_string_distance_parts();
             assert_eq!(operations(self.before, self.after), self.operations);
+            assert_eq!(myers(self.before, self.after), self.operations);
         }
 
         fn assert_string_distance_parts(&self) {
@@ -391,6 +589,14 @@ mod tests {
         Alignment::new(x, y).operations()
     }
 
+    fn myers<'a>(x: &'a str, y: &'a str) -> Vec<Operation> {
+        let (x, y) = (
+            x.graphemes(true).collect::<Vec<&str>>(),
+            y.graphemes(true).collect::<Vec<&str>>(),
+        );
+        myers_operations(&x, &y)
+    }
+
     impl<'a> Alignment<'a> {
         pub fn distance_parts(&self) -> (usize, usize) {
             let (mut numer, mut denom) = (0, 0);
@@ -34,6 +34,36 @@ const TERM_FALLBACK_WIDTH: usize = 79;
     max_term_width = usize::MAX,
 )]
 pub struct Opt {
+    #[arg(
+        long = "binary-hexdump-max-size",
+        default_value = "0",
+        value_name = "N"
+    )]
+    /// Display a hexdump-style diff of binary files of at most N bytes.
+    ///
+    /// The rows of 16 bytes which differ between the old and new versions of the file are
+    /// displayed. The file contents are read as for --binary-summary. With the default of 0 no
+    /// hexdump is displayed.
+    pub binary_hexdump_max_size: usize,
+
+    #[arg(long = "binary-summary")]
+    /// Display the size, type and image dimensions of changed binary files.
+    ///
+    /// The file contents are read from the git object database using the blob ids in the diff's
+    /// "index" line, or from the working tree for the new version of the file. The type is detected
+    /// from the initial bytes of the file (e.g. image/png or application/pdf).
+    pub binary_summary: bool,
+
+    #[arg(
+        long = "binary-summary-style",
+        default_value = "blue",
+        value_name = "STYLE"
+    )]
+    /// Style string for the binary file summary.
+    ///
+    /// See --binary-summary and --binary-hexdump-max-size.
+    pub binary_summary_style: String,
+
     #[arg(long = "blame-code-style", value_name = "STYLE")]
     /// Style string for the code section of a git blame line.
     ///
@@ -96,6 +126,37 @@ pub struct Opt {
     /// See: <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>
     pub blame_timestamp_output_format: Option<String>,
 
+    #[arg(long = "collapse-files", default_value = "", value_name = "GLOBS")]
+    /// Collapse the hunks of files whose paths match any of these space-separated glob patterns.
+    ///
+    /// For example `--collapse-files '*.pb.go *.min.js vendor/**'`. The file header is still
+    /// displayed, but the hunks are replaced by a single line stating the number of changed lines.
+    /// See also --collapse-generated-files.
+    pub collapse_files: String,
+
+    #[arg(long = "collapse-generated-files")]
+    /// Collapse the hunks of files marked as generated, or as not to be diffed, in .gitattributes.
+    ///
+    /// These are files with the `linguist-generated` attribute set, or the `diff` attribute unset
+    /// (e.g. `*.snap -diff`). Their hunks are displayed as for --collapse-files.
+    pub collapse_generated_files: bool,
+
+    #[arg(long = "collapse-lockfile-hunks")]
+    /// Omit the hunks of lockfiles for which a package summary is displayed.
+    ///
+    /// Implies --lockfile-summary. Only the table of package version changes is displayed.
+    pub collapse_lockfile_hunks: bool,
+
+    #[arg(
+        long = "collapsed-file-style",
+        default_value = "blue",
+        value_name = "STYLE"
+    )]
+    /// Style string for the line replacing the hunks of a collapsed file.
+    ///
+    /// See --collapse-files and --collapse-generated-files.
+    pub collapsed_file_style: String,
+
     #[arg(long = "color-only")]
     /// Do not alter the input structurally in any way.
     ///
@@ -202,6 +263,23 @@ pub struct Opt {
     /// If a relativized file path exceeds this width then the diff stat will be misaligned.
     pub diff_stat_align_width: usize,
 
+    #[arg(long = "exclude", default_value = "", value_name = "GLOBS")]
+    /// Do not display files whose paths match any of these space-separated glob patterns.
+    ///
+    /// For example `--exclude '*.snap vendor/**'`. The file header and hunks are omitted, but
+    /// commit metadata is still displayed. For a renamed or copied file, either path may match.
+    /// See also --include and --only.
+    pub exclude: String,
+
+    #[arg(long = "extra-context", default_value = "0", value_name = "N")]
+    /// Display N additional lines of context above and below each hunk.
+    ///
+    /// The lines are read from the file in the working tree if it is the new version of the file
+    /// in the diff, or else from the git object database using the blob id in the diff's "index"
+    /// line. Hunks which become adjacent are merged. Extra context is not displayed for merge
+    /// commits, or when the new version of the file is not available.
+    pub extra_context: usize,
+
     #[arg(long = "features", value_name = "FEATURES")]
     /// Names of delta features to activate (space-separated).
     ///
@@ -355,6 +433,20 @@ pub struct Opt {
     /// lines: set this option to "keep" to keep the original separator symbols.
     pub grep_separator_symbol: String,
 
+    #[arg(
+        long = "hunk-header-breadcrumbs",
+        default_value = "off",
+        value_name = "MODE"
+    )]
+    /// Show the definitions enclosing a hunk (e.g. `impl Painter > fn paint_lines`) in its header.
+    ///
+    /// The breadcrumb is computed from the syntax highlighting scopes of the new version of the
+    /// file, which is read from the working tree, or from the repository if the working tree file
+    /// has changed. MODE is 'replace' to display it in place of the code fragment found by git
+    /// (which is still displayed if the file cannot be read), 'append' to display it after the code
+    /// fragment, or 'off'.
+    pub hunk_header_breadcrumbs: String,
+
     #[arg(
         long = "hunk-header-decoration-style",
         default_value = "blue box",
@@ -412,7 +504,10 @@ pub struct Opt {
     /// Following the hyperlink spec for terminal emulators:
     /// <https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda>. By default, file names
     /// and line numbers link to the local file using a file URL, whereas commit hashes link to the
-    /// commit in GitHub, if the remote repository is hosted by GitHub. See
+    /// commit in the web interface of the remote repository, if it is hosted by GitHub, GitLab,
+    /// SourceHut, Codeberg, Bitbucket or Azure DevOps. A self-hosted forge can be recognized by
+    /// mapping its hostname to its kind in git config, e.g. `delta.forge.git.example.com = gitlab`
+    /// (one of github, gitlab, sourcehut, gitea, forgejo, bitbucket, azure-devops). See
     /// --hyperlinks-file-link-format for full control over the file URLs emitted. Hyperlinks are
     /// supported by several common terminal emulators. To make them work, you must use less
     /// version >= 581 with the -R flag (or use -r with older less versions, but this will break
@@ -427,6 +522,15 @@ pub struct Opt {
     /// --hyperlinks-commit-link-format='https://mygitrepo/{commit}/'
     pub hyperlinks_commit_link_format: Option<String>,
 
+    #[arg(long = "hyperlinks-editor", value_name = "EDITOR")]
+    /// Make file hyperlinks open the file in EDITOR (requires --hyperlinks).
+    ///
+    /// This sets --hyperlinks-file-link-format to a URL opening the file at the line, and the
+    /// column where known, in one of: "vscode" (Visual Studio Code), "idea" (JetBrains IDEs),
+    /// "sublime" (Sublime Text, with the subl:// URL handler) or "zed". For other editors, set
+    /// --hyperlinks-file-link-format to a scheme with a handler you have installed.
+    pub hyperlinks_editor: Option<String>,
+
     #[arg(
         long = "hyperlinks-file-link-format",
         default_value = "file://{path}",
@@ -434,14 +538,96 @@ pub struct Opt {
     )]
     /// Format string for file hyperlinks (requires --hyperlinks).
     ///
-    /// Placeholders "{path}" and "{line}" will be replaced by the absolute file path and the line
-    /// number; "{host}" with the hostname delta is currently running on. The default is to create
+    /// Placeholders "{path}", "{line}" and "{column}" will be replaced by the absolute file path,
+    /// the line number and the column of the first match in grep output (1 if unknown); "{host}"
+    /// with the hostname delta is currently running on. The default is to create
     /// a hyperlink containing a standard file URI with only the filename, which your terminal or
     /// OS should handle. You can specify any scheme, such as "file-line://{path}:{line}" and
     /// register an application to handle it. See
     /// <https://dandavison.github.io/delta/hyperlinks.html> for details.
+    ///
+    /// The placeholder "{remote_url}" will be replaced by the URL of the line in the web interface
+    /// of the remote repository (see --hyperlinks for the recognized forges), at the commit being
+    /// displayed or else at HEAD. For example --hyperlinks-file-link-format='{remote_url}'. If the
+    /// file is not in a repository with a recognized remote, the local file URL is used instead.
     pub hyperlinks_file_link_format: String,
 
+    #[arg(long = "hyperlinks-issue-link-format", value_name = "MAP")]
+    /// Issue references to hyperlink in commit messages, and their URLs (requires --hyperlinks).
+    ///
+    /// A list of regular expressions, one per line, each followed by "=>" and a URL in which the
+    /// placeholder "{issue}" will be replaced by the first capture group of the match, or the
+    /// whole match if there is none. For example
+    /// --hyperlinks-issue-link-format='JIRA-[0-9]+ => https://jira.example.com/browse/{issue}'.
+    /// Lines are separated by newlines, since commas may occur in regular expressions; in git
+    /// config, write them as "\n" in a quoted value.
+    /// By default, references such as "#123" (and "GH-123" on GitHub) link to the issue tracker of
+    /// the remote repository, if it is recognized (see --hyperlinks). Email addresses in trailers
+    /// such as "Co-authored-by:" link to a mailto URL.
+    pub hyperlinks_issue_link_format: Option<String>,
+
+    #[arg(long = "ignore-indentation")]
+    /// Ignore changes in indentation when highlighting changed lines.
+    ///
+    /// Leading whitespace is disregarded when pairing removed and added lines and when computing
+    /// within-line edits, so that re-indented code is not shown as entirely changed. A pair of lines
+    /// that differ only in their indentation is displayed in indentation-change-style. Unlike git
+    /// diff -w, such lines are still shown. See also --indentation-change-hint.
+    pub ignore_indentation: bool,
+
+    #[arg(long = "include", value_name = "GLOBS")]
+    /// Only display files whose paths match any of these space-separated glob patterns.
+    ///
+    /// For example `--include 'src/** tests/**'`. Other files are omitted as for --exclude, which
+    /// takes precedence. For a renamed or copied file, either path may match.
+    pub include: Option<String>,
+
+    #[arg(long = "indentation-change-hint")]
+    /// Annotate lines whose only change is their indentation with the indentation delta.
+    ///
+    /// Requires --ignore-indentation. The hint (e.g. "indentation +4") is displayed after the added
+    /// line in inline-hint-style. Hints are not displayed in side-by-side mode.
+    pub indentation_change_hint: bool,
+
+    #[arg(
+        long = "indentation-change-style",
+        default_value = "dim",
+        value_name = "STYLE"
+    )]
+    /// Style string for lines whose only change is their indentation.
+    ///
+    /// Used with --ignore-indentation, for both the removed and the added line. See STYLES section.
+    pub indentation_change_style: String,
+
+    #[arg(long = "inline-blame")]
+    /// Annotate removed and unchanged lines with the commit that last changed them.
+    ///
+    /// The annotations are displayed in a column to the left of the line numbers: this option
+    /// implies --line-numbers, which it turns on even if line-numbers is false in git config. They
+    /// are computed with git blame, of the old version of each file as of the parent of the commit
+    /// being displayed, or as of HEAD when the diff does not belong to a commit. See
+    /// --inline-blame-format and --inline-blame-style.
+    pub inline_blame: bool,
+
+    #[arg(
+        long = "inline-blame-format",
+        default_value = "{author:<15.14} {timestamp:<15.14}",
+        value_name = "FMT"
+    )]
+    /// Format string for the annotations of --inline-blame.
+    ///
+    /// Available placeholders are "{timestamp}", "{author}", and "{commit}", as for
+    /// --blame-format.
+    pub inline_blame_format: String,
+
+    #[arg(
+        long = "inline-blame-style",
+        default_value = "dim",
+        value_name = "STYLE"
+    )]
+    /// Style string for the annotations of --inline-blame.
+    pub inline_blame_style: String,
+
     #[arg(
         long = "inline-hint-style",
         default_value = "blue",
@@ -473,6 +659,25 @@ pub struct Opt {
     /// output.
     pub keep_plus_minus_markers: bool,
 
+    #[arg(long = "lfs-summary")]
+    /// Summarize the changes to files tracked by Git LFS.
+    ///
+    /// The diff of a file tracked by Git LFS is a diff of its pointer text. Delta instead shows
+    /// which object changed, with the old and new sizes and object id prefixes. The output for a
+    /// small hunk at the start of a file is held back until it is known whether the file is a
+    /// pointer file.
+    pub lfs_summary: bool,
+
+    #[arg(
+        long = "lfs-summary-style",
+        default_value = "blue",
+        value_name = "STYLE"
+    )]
+    /// Style string for the summary replacing the diff of a Git LFS pointer file.
+    ///
+    /// See --lfs-summary and the STYLES section.
+    pub lfs_summary_style: String,
+
     #[arg(long = "light")]
     /// Use default colors appropriate for a light terminal background.
     ///
@@ -497,6 +702,20 @@ pub struct Opt {
     /// --width=variable is given.
     pub line_fill_method: Option<String>,
 
+    #[arg(
+        long = "line-pairing",
+        default_value = "greedy",
+        value_name = "STRATEGY"
+    )]
+    /// How removed and added lines are paired for within-line highlighting.
+    ///
+    /// Possible values are "greedy" and "optimal". With "greedy", each removed line is paired with
+    /// the first following added line within --max-line-distance. With "optimal", pairs are
+    /// chosen to maximize the total similarity of paired lines in the whole block, so that
+    /// inserted or reordered lines do not cause wrong pairings. This is slower; very large blocks
+    /// fall back to "greedy".
+    pub line_pairing: String,
+
     #[arg(short = 'n', long = "line-numbers")]
     /// Display line numbers next to the diff.
     ///
@@ -585,6 +804,24 @@ pub struct Opt {
     /// List available syntax-highlighting color themes.
     pub list_syntax_themes: bool,
 
+    #[arg(long = "lockfile-summary")]
+    /// Summarize package version changes in lockfiles.
+    ///
+    /// For Cargo.lock, package-lock.json (or npm-shrinkwrap.json) and go.sum files, a table of
+    /// added (+), removed (-) and changed (~) packages is displayed after the file header. It is
+    /// computed from the lines present in the hunks. See also --collapse-lockfile-hunks.
+    pub lockfile_summary: bool,
+
+    #[arg(
+        long = "lockfile-summary-style",
+        default_value = "blue",
+        value_name = "STYLE"
+    )]
+    /// Style string for the rows of the lockfile summary table.
+    ///
+    /// See --lockfile-summary and the STYLES section.
+    pub lockfile_summary_style: String,
+
     #[arg(long = "map-styles", value_name = "STYLES_MAP")]
     /// Map styles encountered in raw input to desired output styles.
     ///
@@ -729,6 +966,19 @@ pub struct Opt {
     /// --file-added-label, --file-copied-label, --file-modified-label, --file-removed-label, --file-renamed-label.
     pub navigate: bool,
 
+    #[arg(
+        long = "navigate-levels",
+        default_value = "commit,file,hunk",
+        value_name = "LEVELS"
+    )]
+    /// Stop points of diff navigation (see --navigate).
+    ///
+    /// A comma-separated list of: "commit" (commit lines), "file" (file labels), "hunk" (the
+    /// --hunk-label in hunk headers) and "conflict" (the bar of --merge-conflict-begin-symbol
+    /// preceding a merge conflict). For example, use --navigate-levels=hunk to jump hunk by hunk
+    /// in a large diff of a single file. Ignored if --navigate-regex is set.
+    pub navigate_levels: String,
+
     #[arg(long = "navigate-regex", value_name = "REGEX")]
     /// Regular expression defining navigation stop points.
     pub navigate_regex: Option<String>,
@@ -739,12 +989,44 @@ pub struct Opt {
     /// See GIT CONFIG section.
     pub no_gitconfig: bool,
 
+    #[arg(long = "only", value_name = "CHANGES")]
+    /// Only display files with these comma-separated types of change.
+    ///
+    /// The types are 'added', 'removed', 'modified', 'renamed' and 'copied'; for example
+    /// `--only added,renamed`. Other files are omitted as for --exclude.
+    pub only: Option<String>,
+
+    #[arg(long = "output", value_name = "FILE")]
+    /// Write the output to FILE instead of displaying it.
+    ///
+    /// The file contains the output as it would have been displayed, with its colors and
+    /// decorations, for example to keep a rendered diff as a CI artifact or to share it. It can be
+    /// displayed later with --replay, or with `less -R`. No pager is started.
+    pub output: Option<PathBuf>,
+
+    #[arg(long = "output-sections")]
+    /// With --output, also write where the commits, files and hunks start.
+    ///
+    /// They are written as JSON to FILE.sections.json, in a list of objects with the `section`
+    /// ("commit", "file" or "hunk") and the `line` (starting from 1) and byte `offset` of its
+    /// header in FILE. --replay uses them to navigate with --pager=builtin.
+    pub output_sections: bool,
+
     #[arg(long = "pager", value_name = "CMD")]
     /// Which pager to use.
     ///
     /// The default pager is `less`. You can also change pager by setting the
     /// environment variable DELTA_PAGER, or PAGER. This option overrides these
     /// environment variables.
+    ///
+    /// --pager=builtin selects a pager built into delta, which knows the structure of its output:
+    /// `]` and `[` jump to the next and previous file, `}` and `{` to the next and previous hunk,
+    /// `)` and `(` to the next and previous commit; `c` collapses (or expands) the file at the top
+    /// of the screen; `/` searches for a regular expression, highlighting its matches, and `n` and
+    /// `N` move to the next and previous match; `q` quits. The usual keys of less scroll (j, k,
+    /// space, b, d, u, g, G and the arrow and page keys). Hyperlinks are passed to the terminal.
+    /// As with less, output is read as far as is needed for the screen being displayed, so delta
+    /// waits for the user to scroll further, and the output read so far is held in memory.
     pub pager: Option<String>,
 
     #[arg(
@@ -811,6 +1093,14 @@ pub struct Opt {
     /// This is mainly intended for testing delta.
     pub raw: bool,
 
+    #[arg(long = "replay", value_name = "FILE")]
+    /// Display output written to FILE with --output, in the pager.
+    ///
+    /// If FILE.sections.json was written with --output-sections, jumping between commits, files
+    /// and hunks works in the built-in pager (--pager=builtin); with less, --navigate works if the
+    /// output was written with it. Cannot be used with --output.
+    pub replay: Option<PathBuf>,
+
     #[arg(long = "relative-paths")]
     /// Output all file paths relative to the current directory.
     ///
@@ -823,6 +1113,15 @@ pub struct Opt {
     /// For example, a unified diff heading, a rename, or a chmod.
     pub right_arrow: String,
 
+    #[arg(long = "semantic-marks")]
+    /// Emit terminal semantic marks (OSC 133) at commit, file and hunk headers.
+    ///
+    /// Terminals supporting OSC 133 marks, such as WezTerm, kitty and iTerm2, can then jump
+    /// between the headers in their scrollback, with the key bindings they use to jump between
+    /// shell prompts. This is intended for use with --paging=never; pagers generally do not pass
+    /// the marks through.
+    pub semantic_marks: bool,
+
     #[arg(long = "show-colors")]
     /// Show available named colors.
     ///
@@ -849,6 +1148,7 @@ pub struct Opt {
     /// Show example diff for available delta themes.
     ///
     /// A delta theme is a delta named feature (see --features) that sets either `light` or `dark`.
+    /// Themes are also read from files in $XDG_CONFIG_HOME/delta/themes (see DELTA CONFIG FILE).
     /// See <https://github.com/dandavison/delta#custom-color-themes>. If diff output is supplied on
     /// standard input then this will be used for the demo. For example: `git show | delta
     /// --show-themes`. By default shows dark or light themes only, according to whether delta is in
@@ -860,6 +1160,47 @@ pub struct Opt {
     /// Display diffs in side-by-side layout.
     pub side_by_side: bool,
 
+    #[arg(long = "side-by-side-min-width", default_value = "0", value_name = "N")]
+    /// Minimum width for side-by-side layout when --side-by-side-mode is 'auto'.
+    ///
+    /// If the width available to delta (see --width) is smaller than N, then the unified layout is
+    /// used instead. With the default of 0 the decision is only made per block of changed lines.
+    pub side_by_side_min_width: usize,
+
+    #[arg(
+        long = "side-by-side-mode",
+        default_value = "always",
+        value_name = "MODE"
+    )]
+    /// When side-by-side layout is enabled, whether to use it 'always' or only when it fits ('auto').
+    ///
+    /// In 'auto' mode, the unified layout is used if the terminal is narrower than
+    /// --side-by-side-min-width, and for any block of changed lines containing a line which is too
+    /// long for its panel (such lines would otherwise be wrapped).
+    pub side_by_side_mode: String,
+
+    #[arg(long = "structural-summary")]
+    /// Summarize changes to JSON and TOML files by key path.
+    ///
+    /// A block listing changed keys (e.g. `spec.replicas: 2 → 3`, `+ deps.serde`) is displayed
+    /// after the file header, when both versions of the file parse successfully. The versions are
+    /// read from the repository and the working tree, as for --extra-context. When the old version
+    /// cannot be read, the summary is computed from the lines present in the hunks, so it is only
+    /// shown when the hunks cover the file from its first line to its end without gaps (e.g. for
+    /// small files, or with `git diff -U<n>` for a large n), which is rarely the case at git's
+    /// default of 3 lines of context.
+    pub structural_summary: bool,
+
+    #[arg(
+        long = "structural-summary-style",
+        default_value = "blue",
+        value_name = "STYLE"
+    )]
+    /// Style string for the lines of the structural summary block.
+    ///
+    /// See --structural-summary and the STYLES section.
+    pub structural_summary_style: String,
+
     #[arg(long = "syntax-theme", value_name = "SYNTAX_THEME")]
     /// The syntax-highlighting theme to use.
     ///
@@ -877,6 +1218,22 @@ pub struct Opt {
     /// incorrect.
     pub tab_width: usize,
 
+    #[arg(long = "toc")]
+    /// Display a table of contents before the files of each commit.
+    ///
+    /// The table lists the displayed files with their change (added, removed, renamed, etc) and
+    /// their numbers of added and removed lines. Since the table can only be written once all the
+    /// files are known, the output of a commit is held back until the next commit starts or the
+    /// input ends. With --hyperlinks, the file paths in the table are hyperlinks; with --navigate,
+    /// the file headers remain the stop points for moving from file to file.
+    pub toc: bool,
+
+    #[arg(long = "toc-style", default_value = "blue", value_name = "STYLE")]
+    /// Style string for the lines of the table of contents.
+    ///
+    /// See --toc and the STYLES section.
+    pub toc_style: String,
+
     #[arg(
         long = "true-color",
         default_value = "auto",
@@ -1023,6 +1380,21 @@ By default, delta takes settings from a section named "delta" in git config file
     zero-style = dim syntax
 
 
+{i0}{H_}Delta config file{_H}
+
+Delta also takes settings from $XDG_CONFIG_HOME/delta/config.toml (~/.config/delta/config.toml by default), or from the file named by the DELTA_CONFIG environment variable. It uses the same option names as git config: top-level keys correspond to the [delta] section, and tables to features. An example is
+
+line-numbers = true
+zero-style = "dim syntax"
+
+[my-delta-feature]
+syntax-theme = "Dracula"
+
+Settings in git config take precedence over those in this file.
+
+Themes are read from files in $XDG_CONFIG_HOME/delta/themes. A file named NAME.toml holds the settings of a theme named NAME, in the format above but without tables; a file named *.gitconfig holds [delta "NAME"] sections, like themes.gitconfig. A theme sets dark = true or light = true, and may give its author = "...". Select a theme with --features, as for any feature.
+
+
 {i0}{H_}Features{_H}
 
 A feature is a named collection of delta options in git config. An example is:
@@ -1162,6 +1534,17 @@ For example, the default value of --line-numbers-left-format is '{{nm:^4}}⋮'.
 
 Similarly, the default value of --line-numbers-right-format is '{{np:^4}}│'. This means that the right column should display the plus line number (np), center-aligned, padded with spaces to a width of 4 characters, followed by a unicode dividing-line character (│).
 
+The following placeholders are also available:
+
+{{hn}}:     the number of the hunk within the file, starting at 1
+{{hm}}:     the line number within the hunk, in the old version of the file
+{{hp}}:     the line number within the hunk, in the new version of the file
+{{rm}}:     the distance from the first changed line of the hunk, in the old version of the file, e.g. -2 or +3
+{{rp}}:     the distance from the first changed line of the hunk, in the new version of the file
+{{prefix}}: the unified diff prefix of the line: '-', '+' or ' '
+
+For example, '{{hn}}:{{hm:<3}}' refers to lines as "hunk 3 line 5", and '{{rp:>3}}' gives the offsets for vim's :+N navigation.
+
 Use '<' for left-align, '^' for center-align, and '>' for right-align.
 
 
@@ -1361,13 +1744,17 @@ impl Opt {
     fn from_clap_and_git_config(
         env: &DeltaEnv,
         arg_matches: clap::ArgMatches,
-        mut git_config: Option<GitConfig>,
+        git_config: Option<GitConfig>,
         assets: HighlightingAssets,
     ) -> Self {
         let mut opt = Opt::from_arg_matches(&arg_matches)
             .unwrap_or_else(|_| delta_unreachable("Opt::from_arg_matches failed"));
         opt.env = env.clone();
+        let (mut git_config, skipped_theme_files) = GitConfig::with_config_files(git_config, env);
         options::set::set_options(&mut opt, &mut git_config, &arg_matches, assets);
+        for skipped_theme_file in &skipped_theme_files {
+            skipped_theme_file.warn_if_relevant(&opt);
+        }
         opt.git_config = git_config;
         opt
     }
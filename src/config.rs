@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use clap::parser::ValueSource;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
 use syntect::highlighting::Style as SyntectStyle;
 use syntect::highlighting::Theme as SyntaxTheme;
@@ -11,13 +12,16 @@ use crate::ansi;
 use crate::cli;
 use crate::color::{self, ColorMode};
 use crate::delta::State;
+use crate::edits;
 use crate::fatal;
-use crate::features::navigate;
+use crate::features::hyperlinks;
+use crate::features::navigate::{self, NavigateLevel};
 use crate::features::side_by_side::{self, ansifill, LeftRight};
 use crate::git_config::GitConfig;
 use crate::handlers;
 use crate::handlers::blame::parse_blame_line_numbers;
 use crate::handlers::blame::BlameLineNumbers;
+use crate::handlers::diff_header::FileEvent;
 use crate::minusplus::MinusPlus;
 use crate::paint::BgFillMethod;
 use crate::parse_styles;
@@ -26,6 +30,7 @@ use crate::style::Style;
 use crate::tests::TESTING;
 use crate::utils;
 use crate::utils::bat::output::PagingMode;
+use crate::utils::builtin_pager::BUILTIN_PAGER;
 use crate::utils::regex_replacement::RegexReplacement;
 use crate::wrapping::WrapConfig;
 
@@ -38,6 +43,9 @@ pub const SYNTAX_FALLBACK_LANG: &str = "txt";
 pub struct Config {
     pub available_terminal_width: usize,
     pub background_color_extends_to_terminal_width: bool,
+    pub binary_hexdump_max_size: usize,
+    pub binary_summary: bool,
+    pub binary_summary_style: Style,
     pub blame_code_style: Option<Style>,
     pub blame_format: String,
     pub blame_separator_format: BlameLineNumbers,
@@ -45,6 +53,10 @@ pub struct Config {
     pub blame_separator_style: Option<Style>,
     pub blame_timestamp_format: String,
     pub blame_timestamp_output_format: Option<String>,
+    pub collapse_files: GlobSet,
+    pub collapse_generated_files: bool,
+    pub collapse_lockfile_hunks: bool,
+    pub collapsed_file_style: Style,
     pub color_only: bool,
     pub commit_regex: Regex,
     pub commit_style: Style,
@@ -56,6 +68,8 @@ pub struct Config {
     pub diff_args: String,
     pub diff_stat_align_width: usize,
     pub error_exit_code: i32,
+    pub exclude: GlobSet,
+    pub extra_context: usize,
     pub file_added_label: String,
     pub file_copied_label: String,
     pub file_modified_label: String,
@@ -79,6 +93,7 @@ pub struct Config {
     pub grep_separator_symbol: String,
     pub handle_merge_conflicts: bool,
     pub hostname: Option<String>,
+    pub hunk_header_breadcrumbs: HunkHeaderBreadcrumbs,
     pub hunk_header_file_style: Style,
     pub hunk_header_line_number_style: Style,
     pub hunk_header_style_include_file_path: HunkHeaderIncludeFilePath,
@@ -88,17 +103,30 @@ pub struct Config {
     pub hunk_label: String,
     pub hyperlinks_commit_link_format: Option<String>,
     pub hyperlinks_file_link_format: String,
+    pub hyperlinks_issue_link_format: Vec<(Regex, String)>,
     pub hyperlinks: bool,
+    pub ignore_indentation: bool,
+    pub include: Option<GlobSet>,
+    pub indentation_change_hint: bool,
+    pub indentation_change_style: Style,
+    pub inline_blame: bool,
+    pub inline_blame_format: String,
+    pub inline_blame_style: Style,
     pub inline_hint_style: Style,
     pub inspect_raw_lines: cli::InspectRawLines,
     pub keep_plus_minus_markers: bool,
+    pub lfs_summary: bool,
+    pub lfs_summary_style: Style,
     pub line_buffer_size: usize,
     pub line_fill_method: BgFillMethod,
+    pub line_pairing: edits::LinePairing,
     pub line_numbers_format: LeftRight<String>,
     pub line_numbers_style_leftright: LeftRight<Style>,
     pub line_numbers_style_minusplus: MinusPlus<Style>,
     pub line_numbers_zero_style: Style,
     pub line_numbers: bool,
+    pub lockfile_summary: bool,
+    pub lockfile_summary_style: Style,
     pub styles_map: Option<HashMap<style::AnsiTermStyleEqualityKey, Style>>,
     pub max_line_distance_for_naively_paired_lines: f64,
     pub max_line_distance: f64,
@@ -117,6 +145,9 @@ pub struct Config {
     pub navigate: bool,
     pub null_style: Style,
     pub null_syntect_style: SyntectStyle,
+    pub only: Option<Vec<FileEvent>>,
+    pub output: Option<PathBuf>,
+    pub output_sections: bool,
     pub pager: Option<String>,
     pub paging_mode: PagingMode,
     pub plus_emph_style: Style,
@@ -125,12 +156,20 @@ pub struct Config {
     pub plus_non_emph_style: Style,
     pub plus_style: Style,
     pub relative_paths: bool,
+    pub replay: Option<PathBuf>,
+    pub section_markers: bool,
+    pub semantic_marks: bool,
     pub show_themes: bool,
     pub side_by_side_data: side_by_side::SideBySideData,
     pub side_by_side: bool,
+    pub side_by_side_auto: bool,
+    pub structural_summary: bool,
+    pub structural_summary_style: Style,
     pub syntax_set: SyntaxSet,
     pub syntax_theme: Option<SyntaxTheme>,
     pub tab_cfg: utils::tabs::TabCfg,
+    pub toc: bool,
+    pub toc_style: Style,
     pub tokenization_regex: Regex,
     pub true_color: bool,
     pub truncation_symbol: String,
@@ -164,6 +203,13 @@ pub enum HunkHeaderIncludeCodeFragment {
     No,
 }
 
+#[cfg_attr(test, derive(Clone))]
+pub enum HunkHeaderBreadcrumbs {
+    Replace,
+    Append,
+    Off,
+}
+
 impl Config {
     pub fn get_style(&self, state: &State) -> &Style {
         match state {
@@ -191,6 +237,26 @@ impl From<cli::Opt> for Config {
 
         let wrap_config = WrapConfig::from_opt(&opt, styles["inline-hint-style"]);
 
+        if opt.output.is_some() && opt.replay.is_some() {
+            fatal("Options --output and --replay cannot be used together.");
+        }
+        // Output written to a file is not paged.
+        let paging_mode = if opt.output.is_some() {
+            PagingMode::Never
+        } else {
+            opt.computed.paging_mode
+        };
+        let builtin_pager = paging_mode != PagingMode::Never
+            && opt
+                .pager
+                .as_ref()
+                .or(opt.env.pagers.0.as_ref())
+                .or(opt.env.pagers.1.as_ref())
+                .is_some_and(|pager| pager == BUILTIN_PAGER);
+        // Markers of the starts of commits, files and hunks, for the built-in pager or for the
+        // sections written with --output-sections.
+        let section_markers = builtin_pager || opt.output.is_some() && opt.output_sections;
+
         let max_line_distance_for_naively_paired_lines = opt
             .env
             .experimental_max_line_distance_for_naively_paired_lines
@@ -222,6 +288,12 @@ impl From<cli::Opt> for Config {
             fatal("Option 'blame-palette' must not be empty.")
         }
 
+        let collapse_files = make_glob_set(&opt.collapse_files);
+        let exclude = make_glob_set(&opt.exclude);
+        let include = opt.include.as_deref().map(make_glob_set);
+        let only = opt.only.as_deref().map(parse_file_events);
+        let navigate_levels = parse_navigate_levels(&opt.navigate_levels);
+
         let file_added_label = opt.file_added_label;
         let file_copied_label = opt.file_copied_label;
         let file_modified_label = opt.file_modified_label;
@@ -237,6 +309,27 @@ impl From<cli::Opt> for Config {
             _ => fatal("Invalid option for line-fill-method: Expected \"ansi\" or \"spaces\"."),
         };
 
+        let line_pairing = match opt.line_pairing.as_ref() {
+            "greedy" => edits::LinePairing::Greedy,
+            "optimal" => edits::LinePairing::Optimal,
+            _ => fatal("Invalid option for line-pairing: Expected \"greedy\" or \"optimal\"."),
+        };
+
+        let side_by_side_auto = match opt.side_by_side_mode.as_ref() {
+            "always" => false,
+            "auto" => true,
+            _ => fatal("Invalid option for side-by-side-mode: Expected \"always\" or \"auto\"."),
+        };
+
+        let hunk_header_breadcrumbs = match opt.hunk_header_breadcrumbs.as_ref() {
+            "replace" => HunkHeaderBreadcrumbs::Replace,
+            "append" => HunkHeaderBreadcrumbs::Append,
+            "off" => HunkHeaderBreadcrumbs::Off,
+            _ => fatal(
+                "Invalid option for hunk-header-breadcrumbs: Expected \"replace\", \"append\" or \"off\".",
+            ),
+        };
+
         let side_by_side_data = side_by_side::SideBySideData::new_sbs(
             &opt.computed.decorations_width,
             &opt.computed.available_terminal_width,
@@ -250,14 +343,16 @@ impl From<cli::Opt> for Config {
         let navigate_regex = if (opt.navigate || opt.show_themes)
             && (opt.navigate_regex.is_none() || opt.navigate_regex == Some("".to_string()))
         {
-            Some(navigate::make_navigate_regex(
+            navigate::make_navigate_regex(
                 opt.show_themes,
+                &navigate_levels,
                 &file_modified_label,
                 &file_added_label,
                 &file_removed_label,
                 &file_renamed_label,
                 &hunk_label,
-            ))
+                &opt.merge_conflict_begin_symbol,
+            )
         } else {
             opt.navigate_regex
         };
@@ -286,6 +381,9 @@ impl From<cli::Opt> for Config {
             background_color_extends_to_terminal_width: opt
                 .computed
                 .background_color_extends_to_terminal_width,
+            binary_hexdump_max_size: opt.binary_hexdump_max_size,
+            binary_summary: opt.binary_summary,
+            binary_summary_style: styles["binary-summary-style"],
             blame_format: opt.blame_format,
             blame_code_style: styles.remove("blame-code-style"),
             blame_palette,
@@ -294,6 +392,10 @@ impl From<cli::Opt> for Config {
             blame_timestamp_format: opt.blame_timestamp_format,
             blame_timestamp_output_format: opt.blame_timestamp_output_format,
             commit_style: styles["commit-style"],
+            collapse_files,
+            collapse_generated_files: opt.collapse_generated_files,
+            collapse_lockfile_hunks: opt.collapse_lockfile_hunks,
+            collapsed_file_style: styles["collapsed-file-style"],
             color_only: opt.color_only,
             commit_regex,
             cwd_of_delta_process,
@@ -304,6 +406,8 @@ impl From<cli::Opt> for Config {
             diff_args: opt.diff_args,
             diff_stat_align_width: opt.diff_stat_align_width,
             error_exit_code: 2, // Use 2 for error because diff uses 0 and 1 for non-error.
+            exclude,
+            extra_context: opt.extra_context,
             file_added_label,
             file_copied_label,
             file_modified_label,
@@ -329,6 +433,7 @@ impl From<cli::Opt> for Config {
             grep_separator_symbol: opt.grep_separator_symbol,
             handle_merge_conflicts: !opt.raw,
             hostname: opt.env.hostname,
+            hunk_header_breadcrumbs,
             hunk_header_file_style: styles["hunk-header-file-style"],
             hunk_header_line_number_style: styles["hunk-header-line-number-style"],
             hunk_header_style: styles["hunk-header-style"],
@@ -361,10 +466,32 @@ impl From<cli::Opt> for Config {
             },
             hyperlinks: opt.hyperlinks,
             hyperlinks_commit_link_format: opt.hyperlinks_commit_link_format,
-            hyperlinks_file_link_format: opt.hyperlinks_file_link_format,
+            hyperlinks_file_link_format: match &opt.hyperlinks_editor {
+                Some(editor) => hyperlinks::editor_file_link_format(editor)
+                    .unwrap_or_else(|| {
+                        fatal(format!(
+                            "Invalid value for --hyperlinks-editor: '{editor}'. Expected one of \
+                             'vscode', 'idea', 'sublime' or 'zed'."
+                        ))
+                    })
+                    .to_string(),
+                None => opt.hyperlinks_file_link_format,
+            },
+            hyperlinks_issue_link_format: parse_issue_link_formats(
+                opt.hyperlinks_issue_link_format.as_deref(),
+            ),
             inspect_raw_lines: opt.computed.inspect_raw_lines,
+            ignore_indentation: opt.ignore_indentation,
+            include,
+            indentation_change_hint: opt.indentation_change_hint,
+            indentation_change_style: styles["indentation-change-style"],
+            inline_blame: opt.inline_blame,
+            inline_blame_format: opt.inline_blame_format,
+            inline_blame_style: styles["inline-blame-style"],
             inline_hint_style: styles["inline-hint-style"],
             keep_plus_minus_markers: opt.keep_plus_minus_markers,
+            lfs_summary: opt.lfs_summary,
+            lfs_summary_style: styles["lfs-summary-style"],
             line_fill_method: if !opt.computed.stdout_is_term && !TESTING {
                 // Don't write ANSI sequences (which rely on the width of the
                 // current terminal) into a file. Also see UseFullPanelWidth.
@@ -373,7 +500,11 @@ impl From<cli::Opt> for Config {
             } else {
                 line_fill_method
             },
-            line_numbers: opt.line_numbers && !handlers::hunk::is_word_diff(),
+            // --inline-blame implies line numbers, even if they are turned off in git config.
+            line_numbers: (opt.line_numbers || opt.inline_blame) && !handlers::hunk::is_word_diff(),
+            line_pairing,
+            lockfile_summary: opt.lockfile_summary || opt.collapse_lockfile_hunks,
+            lockfile_summary_style: styles["lockfile-summary-style"],
             line_numbers_format: LeftRight::new(
                 opt.line_numbers_left_format,
                 opt.line_numbers_right_format,
@@ -413,8 +544,11 @@ impl From<cli::Opt> for Config {
             navigate_regex,
             null_style: Style::new(),
             null_syntect_style: SyntectStyle::default(),
+            only,
+            output: opt.output,
+            output_sections: opt.output_sections,
             pager: opt.pager,
-            paging_mode: opt.computed.paging_mode,
+            paging_mode,
             plus_emph_style: styles["plus-emph-style"],
             plus_empty_line_marker_style: styles["plus-empty-line-marker-style"],
             plus_file: opt.plus_file,
@@ -423,13 +557,21 @@ impl From<cli::Opt> for Config {
             git_minus_style: styles["git-minus-style"],
             git_plus_style: styles["git-plus-style"],
             relative_paths: opt.relative_paths,
+            replay: opt.replay,
+            section_markers,
+            semantic_marks: opt.semantic_marks,
             show_themes: opt.show_themes,
             side_by_side: opt.side_by_side && !handlers::hunk::is_word_diff(),
+            side_by_side_auto,
+            structural_summary: opt.structural_summary,
+            structural_summary_style: styles["structural-summary-style"],
             side_by_side_data,
             styles_map,
             syntax_set: opt.computed.syntax_set,
             syntax_theme: opt.computed.syntax_theme,
             tab_cfg: utils::tabs::TabCfg::new(opt.tab_width),
+            toc: opt.toc,
+            toc_style: styles["toc-style"],
             tokenization_regex,
             true_color: opt.computed.true_color,
             truncation_symbol: format!("{}→{}", ansi::ANSI_SGR_REVERSE, ansi::ANSI_SGR_RESET),
@@ -440,6 +582,74 @@ impl From<cli::Opt> for Config {
     }
 }
 
+fn make_glob_set(globs: &str) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs.split_whitespace() {
+        match Glob::new(glob) {
+            Ok(glob) => builder.add(glob),
+            Err(err) => fatal(format!("Invalid glob pattern '{glob}': {err}")),
+        };
+    }
+    builder
+        .build()
+        .unwrap_or_else(|err| fatal(format!("Invalid glob patterns '{globs}': {err}")))
+}
+
+fn parse_issue_link_formats(issue_link_formats: Option<&str>) -> Vec<(Regex, String)> {
+    issue_link_formats
+        .unwrap_or_default()
+        .lines()
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| match pair.split_once("=>") {
+            Some((regex, url_format)) => match Regex::new(regex.trim()) {
+                Ok(regex) => (regex, url_format.trim().to_string()),
+                Err(err) => fatal(format!(
+                    "Invalid regex in --hyperlinks-issue-link-format: '{}': {err}",
+                    regex.trim()
+                )),
+            },
+            None => fatal(format!(
+                "Invalid value for --hyperlinks-issue-link-format: '{}'. Expected \
+                 REGEX => URL pairs on separate lines.",
+                pair.trim()
+            )),
+        })
+        .collect()
+}
+
+fn parse_navigate_levels(levels: &str) -> Vec<NavigateLevel> {
+    levels
+        .split(',')
+        .map(|level| match level.trim() {
+            "commit" => NavigateLevel::Commit,
+            "file" => NavigateLevel::File,
+            "hunk" => NavigateLevel::Hunk,
+            "conflict" => NavigateLevel::Conflict,
+            _ => fatal(format!(
+                "Invalid value for --navigate-levels: '{level}'. Expected a comma-separated list \
+                 of 'commit', 'file', 'hunk' or 'conflict'."
+            )),
+        })
+        .collect()
+}
+
+fn parse_file_events(file_events: &str) -> Vec<FileEvent> {
+    file_events
+        .split(',')
+        .map(|file_event| match file_event.trim() {
+            "added" => FileEvent::Added,
+            "copied" => FileEvent::Copy,
+            "modified" => FileEvent::Change,
+            "removed" => FileEvent::Removed,
+            "renamed" => FileEvent::Rename,
+            _ => fatal(format!(
+                "Invalid value for --only: '{file_event}'. Expected a comma-separated list of \
+                 'added', 'removed', 'modified', 'renamed' or 'copied'."
+            )),
+        })
+        .collect()
+}
+
 fn make_blame_palette(blame_palette: Option<String>, mode: ColorMode) -> Vec<String> {
     match (blame_palette, mode) {
         (Some(string), _) => string
@@ -112,6 +112,21 @@ pub struct StateMachine<'a> {
     pub handled_diff_header_header_line_file_pair: Option<(String, String)>,
     pub blame_key_colors: HashMap<String, String>,
     pub minus_line_counter: AmbiguousDiffMinusCounter,
+    pub file_summary: Option<features::file_summary::FileSummary>,
+    pub minus_blob_id: Option<String>,
+    pub plus_blob_id: Option<String>,
+    pub extra_context: Option<features::extra_context::ExtraContext>,
+    pub breadcrumbs: Option<features::breadcrumbs::Breadcrumbs>,
+    pub collapsed_file: Option<features::collapsed_file::CollapsedFile>,
+    pub lfs_pointer_hunk: Option<features::lfs_pointer::LfsPointerHunk>,
+    pub toc: Option<features::toc::Toc>,
+    // The commit whose diff is being displayed, if known.
+    pub commit: Option<String>,
+    // Whether git's "rename from" or "copy from" header line was seen for the current file.
+    pub rename_or_copy: Option<handlers::diff_header::FileEvent>,
+    pub skip_file: bool,
+    // The numbers of minus and plus lines left in the current hunk of a skipped file.
+    pub skipped_hunk_lines: (usize, usize),
 }
 
 pub fn delta<I>(lines: ByteLines<I>, writer: &mut dyn Write, config: &Config) -> std::io::Result<()>
@@ -140,6 +155,18 @@ impl<'a> StateMachine<'a> {
             config,
             blame_key_colors: HashMap::new(),
             minus_line_counter: AmbiguousDiffMinusCounter::not_needed(),
+            file_summary: None,
+            minus_blob_id: None,
+            plus_blob_id: None,
+            extra_context: None,
+            breadcrumbs: None,
+            collapsed_file: None,
+            lfs_pointer_hunk: None,
+            toc: None,
+            commit: None,
+            rename_or_copy: None,
+            skip_file: false,
+            skipped_hunk_lines: (0, 0),
         }
     }
 
@@ -166,11 +193,13 @@ impl<'a> StateMachine<'a> {
             let _ = self.handle_commit_meta_header_line()?
                 || self.handle_diff_stat_line()?
                 || self.handle_diff_header_diff_line()?
+                || self.handle_skipped_file_line()
                 || self.handle_diff_header_file_operation_line()?
                 || self.handle_diff_header_minus_line()?
                 || self.handle_diff_header_plus_line()?
                 || self.handle_hunk_header_line()?
                 || self.handle_diff_header_mode_line()?
+                || self.handle_diff_header_index_line()
                 || self.handle_diff_header_misc_line()?
                 || self.handle_submodule_log_line()?
                 || self.handle_submodule_short_line()?
@@ -179,6 +208,7 @@ impl<'a> StateMachine<'a> {
                 || self.handle_git_show_file_line()?
                 || self.handle_blame_line()?
                 || self.handle_grep_line()?
+                || self.handle_commit_meta_body_line()?
                 || self.should_skip_line()
                 || self.emit_line_unchanged()?;
         }
@@ -186,6 +216,8 @@ impl<'a> StateMachine<'a> {
         self.handle_pending_line_with_diff_name()?;
         self.painter.paint_buffered_minus_and_plus_lines();
         self.painter.emit()?;
+        self.finish_file()?;
+        self.write_toc()?;
         Ok(())
     }
 
@@ -235,6 +267,17 @@ impl<'a> StateMachine<'a> {
         self.line = ansi::strip_ansi_codes(&self.raw_line);
     }
 
+    /// Complete the output for the current file, when a new file or commit starts, or at the end
+    /// of the input.
+    pub fn finish_file(&mut self) -> std::io::Result<()> {
+        self.painter.paint_deferred_zero_lines();
+        self.painter.emit()?;
+        self.finish_extra_context()?;
+        self.write_collapsed_file_line()?;
+        self.write_lfs_pointer_summary()?;
+        self.write_file_summary()
+    }
+
     /// Skip file metadata lines unless a raw diff style has been requested.
     pub fn should_skip_line(&self) -> bool {
         matches!(self.state, State::DiffHeader(_))
@@ -263,10 +306,16 @@ impl<'a> StateMachine<'a> {
     }
 }
 
+/// Whether hyperlinks should be added to lines emitted unaltered: they are requested, and output
+/// is going to a tty.
+pub fn should_hyperlink_raw_lines(config: &Config) -> bool {
+    config.hyperlinks && io::stdout().is_terminal()
+}
+
 /// If output is going to a tty, emit hyperlinks if requested.
 // Although raw output should basically be emitted unaltered, we do this.
 pub fn format_raw_line<'a>(line: &'a str, config: &Config) -> Cow<'a, str> {
-    if config.hyperlinks && io::stdout().is_terminal() {
+    if should_hyperlink_raw_lines(config) {
         features::hyperlinks::format_commit_line_with_osc8_commit_hyperlink(line, config)
     } else {
         Cow::from(line)
@@ -6,6 +6,21 @@ use unicode_width::UnicodeWidthStr;
 use crate::align;
 use crate::minusplus::MinusPlus;
 
+// Above this number of (minus line, plus line) combinations, optimal pairing falls back to greedy
+// pairing, since every combination must be aligned.
+const MAX_OPTIMAL_PAIRING_SIZE: usize = 1 << 8;
+
+/// Strategy used to decide which minus lines are paired with which plus lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinePairing {
+    /// Each minus line is paired with the first sufficiently similar plus line following the
+    /// previous pair.
+    Greedy,
+    /// Pairs are chosen to maximize the total similarity of paired lines over the whole block,
+    /// preserving line order.
+    Optimal,
+}
+
 /// Infer the edit operations responsible for the differences between a collection of old and new
 /// lines. A "line" is a string. An annotated line is a Vec of (op, &str) pairs, where the &str
 /// slices are slices of the line, and their concatenation equals the line. Return the input minus
@@ -19,6 +34,10 @@ use crate::minusplus::MinusPlus;
 /// `noop_deletions[i]` is the appropriate deletion operation tag to be used for `minus_lines[i]`;
 /// `noop_deletions` is guaranteed to be the same length as `minus_lines`. The equivalent statements
 /// hold for `plus_insertions` and `plus_lines`.
+///
+/// `line_pairing` selects how homologous minus and plus lines are chosen; see [`LinePairing`].
+/// If `ignore_indentation` is true, differences in leading whitespace are disregarded when
+/// pairing lines and are not annotated as edits.
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub fn infer_edits<'a, EditOperation>(
@@ -31,6 +50,8 @@ pub fn infer_edits<'a, EditOperation>(
     tokenization_regex: &Regex,
     max_line_distance: f64,
     max_line_distance_for_naively_paired_lines: f64,
+    line_pairing: LinePairing,
+    ignore_indentation: bool,
 ) -> (
     Vec<Vec<(EditOperation, &'a str)>>,  // annotated minus lines
     Vec<Vec<(EditOperation, &'a str)>>,  // annotated plus lines
@@ -39,6 +60,22 @@ pub fn infer_edits<'a, EditOperation>(
 where
     EditOperation: Copy + PartialEq + std::fmt::Debug,
 {
+    if line_pairing == LinePairing::Optimal
+        && minus_lines.len() * plus_lines.len() <= MAX_OPTIMAL_PAIRING_SIZE
+    {
+        return infer_edits_with_optimal_pairing(
+            minus_lines,
+            plus_lines,
+            noop_deletions,
+            deletion,
+            noop_insertions,
+            insertion,
+            tokenization_regex,
+            max_line_distance,
+            max_line_distance_for_naively_paired_lines,
+            ignore_indentation,
+        );
+    }
     let mut annotated_minus_lines = Vec::<Vec<(EditOperation, &str)>>::new();
     let mut annotated_plus_lines = Vec::<Vec<(EditOperation, &str)>>::new();
     let mut line_alignment = Vec::<(Option<usize>, Option<usize>)>::new();
@@ -48,18 +85,15 @@ where
     'minus_lines_loop: for (minus_index, minus_line) in minus_lines.iter().enumerate() {
         let mut considered = 0; // plus lines considered so far as match for minus_line
         for plus_line in &plus_lines[plus_index..] {
-            let alignment = align::Alignment::new(
-                tokenize(minus_line, tokenization_regex),
-                tokenize(plus_line, tokenization_regex),
-            );
-            let (annotated_minus_line, annotated_plus_line, distance) = annotate(
-                alignment,
+            let (annotated_minus_line, annotated_plus_line, distance) = align_and_annotate(
+                minus_line,
+                plus_line,
                 noop_deletions[minus_index],
                 deletion,
                 noop_insertions[plus_index],
                 insertion,
-                minus_line,
-                plus_line,
+                tokenization_regex,
+                ignore_indentation,
             );
             if minus_lines.len() == plus_lines.len()
                 && distance <= max_line_distance_for_naively_paired_lines
@@ -90,21 +124,216 @@ where
     }
     // Emit any remaining plus lines
     for plus_line in &plus_lines[plus_index..] {
-        if let Some(content) = get_contents_before_trailing_whitespace(plus_line) {
-            annotated_plus_lines.push(vec![
-                (noop_insertions[plus_index], content),
-                (noop_insertions[plus_index], &plus_line[content.len()..]),
-            ]);
+        annotated_plus_lines.push(unpaired_plus_line(plus_line, noop_insertions[plus_index]));
+        line_alignment.push((None, Some(plus_index)));
+        plus_index += 1;
+    }
+
+    (annotated_minus_lines, annotated_plus_lines, line_alignment)
+}
+
+/// As `infer_edits`, but choose the line pairs by dynamic programming over the matrix of minus
+/// and plus line distances, so that the pairing is optimal for the whole block rather than
+/// decided line by line. Pairs may not cross, i.e. line order is preserved. If there are as many
+/// minus as plus lines, `max_line_distance_for_naively_paired_lines` applies only to the naive
+/// pairs, i.e. the i-th minus line and the i-th plus line, so that it does not admit dissimilar
+/// pairs elsewhere in the block.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn infer_edits_with_optimal_pairing<'a, EditOperation>(
+    minus_lines: Vec<&'a str>,
+    plus_lines: Vec<&'a str>,
+    noop_deletions: Vec<EditOperation>,
+    deletion: EditOperation,
+    noop_insertions: Vec<EditOperation>,
+    insertion: EditOperation,
+    tokenization_regex: &Regex,
+    max_line_distance: f64,
+    max_line_distance_for_naively_paired_lines: f64,
+    ignore_indentation: bool,
+) -> (
+    Vec<Vec<(EditOperation, &'a str)>>,
+    Vec<Vec<(EditOperation, &'a str)>>,
+    Vec<(Option<usize>, Option<usize>)>,
+)
+where
+    EditOperation: Copy + PartialEq + std::fmt::Debug,
+{
+    let (n, m) = (minus_lines.len(), plus_lines.len());
+    let max_distance = |i: usize, j: usize| {
+        if n == m && i == j {
+            max_line_distance.max(max_line_distance_for_naively_paired_lines)
+        } else {
+            max_line_distance
+        }
+    };
+
+    // The annotated lines and similarity of each candidate pair, in row-major order, kept so
+    // that the chosen pairs need not be aligned again.
+    let mut annotated_pairs = Vec::with_capacity(n * m);
+    let mut similarity = Vec::with_capacity(n * m);
+    for i in 0..n {
+        for j in 0..m {
+            let (annotated_minus_line, annotated_plus_line, distance) = align_and_annotate(
+                minus_lines[i],
+                plus_lines[j],
+                noop_deletions[i],
+                deletion,
+                noop_insertions[j],
+                insertion,
+                tokenization_regex,
+                ignore_indentation,
+            );
+            if distance <= max_distance(i, j) {
+                annotated_pairs.push(Some((annotated_minus_line, annotated_plus_line)));
+                similarity.push(Some(1.0 - distance));
+            } else {
+                annotated_pairs.push(None);
+                similarity.push(None);
+            }
+        }
+    }
+
+    // score[i][j] is the best total similarity achievable pairing minus_lines[i..] with
+    // plus_lines[j..]. Row-major storage of (n + 1) x (m + 1) table.
+    let index = |i: usize, j: usize| i * (m + 1) + j;
+    let mut score = vec![0.0_f64; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            let unpaired = score[index(i + 1, j)].max(score[index(i, j + 1)]);
+            score[index(i, j)] = match similarity[i * m + j] {
+                Some(s) => unpaired.max(s + score[index(i + 1, j + 1)]),
+                None => unpaired,
+            };
+        }
+    }
+
+    let mut annotated_minus_lines = Vec::<Vec<(EditOperation, &str)>>::with_capacity(n);
+    let mut annotated_plus_lines = Vec::<Vec<(EditOperation, &str)>>::with_capacity(m);
+    let mut line_alignment = Vec::<(Option<usize>, Option<usize>)>::new();
+    let mut unpaired_plus_lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    // Walk the table, preferring to pair, then to leave the minus line unpaired.
+    while i < n && j < m {
+        let paired = similarity[i * m + j].map(|s| s + score[index(i + 1, j + 1)]);
+        if paired == Some(score[index(i, j)]) {
+            for plus_index in unpaired_plus_lines.drain(..) {
+                annotated_plus_lines.push(unpaired_plus_line(
+                    plus_lines[plus_index],
+                    noop_insertions[plus_index],
+                ));
+                line_alignment.push((None, Some(plus_index)));
+            }
+            let (annotated_minus_line, annotated_plus_line) = annotated_pairs[i * m + j]
+                .take()
+                .expect("paired lines have been annotated");
+            annotated_minus_lines.push(annotated_minus_line);
+            annotated_plus_lines.push(annotated_plus_line);
+            line_alignment.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if score[index(i + 1, j)] == score[index(i, j)] {
+            annotated_minus_lines.push(vec![(noop_deletions[i], minus_lines[i])]);
+            line_alignment.push((Some(i), None));
+            i += 1;
         } else {
-            annotated_plus_lines.push(vec![(noop_insertions[plus_index], plus_line)]);
+            unpaired_plus_lines.push(j);
+            j += 1;
         }
+    }
+    for minus_index in i..n {
+        annotated_minus_lines.push(vec![(
+            noop_deletions[minus_index],
+            minus_lines[minus_index],
+        )]);
+        line_alignment.push((Some(minus_index), None));
+    }
+    for plus_index in unpaired_plus_lines.into_iter().chain(j..m) {
+        annotated_plus_lines.push(unpaired_plus_line(
+            plus_lines[plus_index],
+            noop_insertions[plus_index],
+        ));
         line_alignment.push((None, Some(plus_index)));
-        plus_index += 1;
     }
 
     (annotated_minus_lines, annotated_plus_lines, line_alignment)
 }
 
+// Annotate an unpaired plus line, separating any trailing whitespace so that it can be
+// highlighted as a whitespace error.
+fn unpaired_plus_line<EditOperation>(
+    plus_line: &str,
+    noop_insertion: EditOperation,
+) -> Vec<(EditOperation, &str)>
+where
+    EditOperation: Copy,
+{
+    if let Some(content) = get_contents_before_trailing_whitespace(plus_line) {
+        vec![
+            (noop_insertion, content),
+            (noop_insertion, &plus_line[content.len()..]),
+        ]
+    } else {
+        vec![(noop_insertion, plus_line)]
+    }
+}
+
+/// Split a line into its leading whitespace and the remainder. The remainder retains any trailing
+/// newline, and is never empty unless the line is: a line consisting only of whitespace is split
+/// before its newline, and not split if it has none.
+pub fn split_indentation(line: &str) -> (&str, &str) {
+    let content = line.trim_start_matches(|c: char| c.is_whitespace() && c != '\n');
+    if content.is_empty() {
+        ("", line)
+    } else {
+        line.split_at(line.len() - content.len())
+    }
+}
+
+/// Align a minus and a plus line and annotate them. If `ignore_indentation` is true, leading
+/// whitespace is excluded from the alignment and annotated as unchanged.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn align_and_annotate<'a, Annotation>(
+    minus_line: &'a str,
+    plus_line: &'a str,
+    noop_deletion: Annotation,
+    deletion: Annotation,
+    noop_insertion: Annotation,
+    insertion: Annotation,
+    tokenization_regex: &Regex,
+    ignore_indentation: bool,
+) -> (Vec<(Annotation, &'a str)>, Vec<(Annotation, &'a str)>, f64)
+where
+    Annotation: Copy + PartialEq + std::fmt::Debug,
+{
+    let ((minus_indentation, minus_line), (plus_indentation, plus_line)) = if ignore_indentation {
+        (split_indentation(minus_line), split_indentation(plus_line))
+    } else {
+        (("", minus_line), ("", plus_line))
+    };
+    let alignment = align::Alignment::new(
+        tokenize(minus_line, tokenization_regex),
+        tokenize(plus_line, tokenization_regex),
+    );
+    let (mut annotated_minus_line, mut annotated_plus_line, distance) = annotate(
+        alignment,
+        noop_deletion,
+        deletion,
+        noop_insertion,
+        insertion,
+        minus_line,
+        plus_line,
+    );
+    if !minus_indentation.is_empty() {
+        annotated_minus_line.insert(0, (noop_deletion, minus_indentation));
+    }
+    if !plus_indentation.is_empty() {
+        annotated_plus_line.insert(0, (noop_insertion, plus_indentation));
+    }
+    (annotated_minus_line, annotated_plus_line, distance)
+}
+
 // Return `None` if there is no trailing whitespace.
 // Return `Some(content)` where content is trimmed if there was some trailing whitespace
 fn get_contents_before_trailing_whitespace(line: &str) -> Option<&str> {
@@ -895,11 +1124,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_infer_edits_line_pairing_with_inserted_line() {
+        // Greedy pairing pairs the first minus line with the inserted plus line, since it is the
+        // first plus line within max_line_distance; optimal pairing pairs the homologous lines.
+        let minus_lines = vec!["let x = compute(a, b);", "let y = compute(c, d);"];
+        let plus_lines = vec![
+            "let w = compute(e);",
+            "let x = compute(a, bb);",
+            "let y = compute(c, dd);",
+        ];
+        assert_eq!(
+            line_alignment(&minus_lines, &plus_lines, LinePairing::Greedy),
+            vec![(Some(0), Some(0)), (Some(1), Some(1)), (None, Some(2))]
+        );
+        assert_eq!(
+            line_alignment(&minus_lines, &plus_lines, LinePairing::Optimal),
+            vec![(None, Some(0)), (Some(0), Some(1)), (Some(1), Some(2))]
+        );
+    }
+
+    #[test]
+    fn test_infer_edits_optimal_line_pairing_with_unpaired_lines() {
+        let minus_lines = vec!["aaa bbb ccc", "xxx", "ddd eee fff"];
+        let plus_lines = vec!["yyy", "aaa bbb ccc ggg", "zzz", "ddd eee fff hhh"];
+        assert_eq!(
+            line_alignment(&minus_lines, &plus_lines, LinePairing::Optimal),
+            vec![
+                (None, Some(0)),
+                (Some(0), Some(1)),
+                (Some(1), None),
+                (None, Some(2)),
+                (Some(2), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_edits_optimal_line_pairing_with_naively_paired_lines() {
+        // The distance threshold for naively paired lines applies to the i-th minus and plus
+        // lines only: the dissimilar pair of the first minus line and the last plus line does
+        // not displace the naive pairs.
+        let minus_lines = vec!["a b c d e", "one"];
+        let plus_lines = vec!["two", "a x y z w"];
+        let (_, _, line_alignment) = infer_edits(
+            minus_lines,
+            plus_lines,
+            vec![MinusNoop; 2],
+            Deletion,
+            vec![PlusNoop; 2],
+            Insertion,
+            &DEFAULT_TOKENIZATION_REGEXP,
+            0.6,
+            1.0,
+            LinePairing::Optimal,
+            false,
+        );
+        assert_eq!(line_alignment, vec![(Some(0), Some(0)), (Some(1), Some(1))]);
+    }
+
+    #[test]
+    fn test_infer_edits_ignore_indentation() {
+        let minus_lines = vec!["foo(a)\n", "bar(b)\n"];
+        let plus_lines = vec!["    foo(a)\n", "    bar(c)\n"];
+        let (annotated_minus_lines, annotated_plus_lines, _) = infer_edits(
+            minus_lines,
+            plus_lines,
+            vec![MinusNoop; 2],
+            Deletion,
+            vec![PlusNoop; 2],
+            Insertion,
+            &DEFAULT_TOKENIZATION_REGEXP,
+            0.6,
+            0.0,
+            LinePairing::Greedy,
+            true,
+        );
+        assert_eq!(
+            annotated_minus_lines,
+            vec![
+                vec![(MinusNoop, "foo(a)\n")],
+                vec![(MinusNoop, "bar("), (Deletion, "b"), (MinusNoop, ")\n")],
+            ]
+        );
+        assert_eq!(
+            annotated_plus_lines,
+            vec![
+                vec![(PlusNoop, "    "), (PlusNoop, "foo(a)\n")],
+                vec![
+                    (PlusNoop, "    "),
+                    (PlusNoop, "bar("),
+                    (Insertion, "c"),
+                    (PlusNoop, ")\n")
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_indentation() {
+        assert_eq!(split_indentation("  a b\n"), ("  ", "a b\n"));
+        assert_eq!(split_indentation("\t a\n"), ("\t ", "a\n"));
+        assert_eq!(split_indentation("a\n"), ("", "a\n"));
+        assert_eq!(split_indentation("   \n"), ("   ", "\n"));
+        assert_eq!(split_indentation("   "), ("", "   "));
+    }
+
+    fn line_alignment(
+        minus_lines: &[&str],
+        plus_lines: &[&str],
+        line_pairing: LinePairing,
+    ) -> Vec<(Option<usize>, Option<usize>)> {
+        infer_edits(
+            minus_lines.to_vec(),
+            plus_lines.to_vec(),
+            vec![MinusNoop; minus_lines.len()],
+            Deletion,
+            vec![PlusNoop; plus_lines.len()],
+            Insertion,
+            &DEFAULT_TOKENIZATION_REGEXP,
+            0.6,
+            0.0,
+            line_pairing,
+            false,
+        )
+        .2
+    }
+
     fn assert_edits(
         minus_lines: Vec<&str>,
         plus_lines: Vec<&str>,
         expected_edits: Edits,
         max_line_distance: f64,
+    ) {
+        assert_edits_with_line_pairing(
+            minus_lines.clone(),
+            plus_lines.clone(),
+            expected_edits.clone(),
+            max_line_distance,
+            LinePairing::Greedy,
+        );
+        // Optimal pairing agrees with greedy pairing in all of these simple cases.
+        assert_edits_with_line_pairing(
+            minus_lines,
+            plus_lines,
+            expected_edits,
+            max_line_distance,
+            LinePairing::Optimal,
+        );
+    }
+
+    fn assert_edits_with_line_pairing(
+        minus_lines: Vec<&str>,
+        plus_lines: Vec<&str>,
+        expected_edits: Edits,
+        max_line_distance: f64,
+        line_pairing: LinePairing,
     ) {
         let (minus_lines, noop_deletions): (Vec<&str>, Vec<EditOperation>) =
             minus_lines.into_iter().map(|s| (s, MinusNoop)).unzip();
@@ -915,6 +1295,8 @@ mod tests {
             &DEFAULT_TOKENIZATION_REGEXP,
             max_line_distance,
             0.0,
+            line_pairing,
+            false,
         );
         // compare_annotated_lines(actual_edits, expected_edits);
         // TODO: test line alignment
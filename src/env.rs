@@ -9,12 +9,16 @@ const DELTA_NAVIGATE: &str = "DELTA_NAVIGATE";
 const DELTA_EXPERIMENTAL_MAX_LINE_DISTANCE_FOR_NAIVELY_PAIRED_LINES: &str =
     "DELTA_EXPERIMENTAL_MAX_LINE_DISTANCE_FOR_NAIVELY_PAIRED_LINES";
 const DELTA_PAGER: &str = "DELTA_PAGER";
+const DELTA_CONFIG: &str = "DELTA_CONFIG";
+const XDG_CONFIG_HOME: &str = "XDG_CONFIG_HOME";
 
 #[derive(Default, Clone)]
 pub struct DeltaEnv {
     pub bat_theme: Option<String>,
     pub colorterm: Option<String>,
+    pub config_home: Option<std::path::PathBuf>,
     pub current_dir: Option<std::path::PathBuf>,
+    pub delta_config: Option<std::path::PathBuf>,
     pub experimental_max_line_distance_for_naively_paired_lines: Option<String>,
     pub features: Option<String>,
     pub git_config_parameters: Option<String>,
@@ -29,6 +33,13 @@ impl DeltaEnv {
     pub fn init() -> Self {
         let bat_theme = env::var(BAT_THEME).ok();
         let colorterm = env::var(COLORTERM).ok();
+        let config_home = env::var_os(XDG_CONFIG_HOME)
+            .map(std::path::PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| dirs::home_dir().map(|dir| dir.join(".config")));
+        let delta_config = env::var_os(DELTA_CONFIG)
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from);
         let experimental_max_line_distance_for_naively_paired_lines =
             env::var(DELTA_EXPERIMENTAL_MAX_LINE_DISTANCE_FOR_NAIVELY_PAIRED_LINES).ok();
         let features = env::var(DELTA_FEATURES).ok();
@@ -50,7 +61,9 @@ impl DeltaEnv {
         Self {
             bat_theme,
             colorterm,
+            config_home,
             current_dir,
+            delta_config,
             experimental_max_line_distance_for_naively_paired_lines,
             features,
             git_config_parameters,
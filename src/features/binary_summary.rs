@@ -0,0 +1,353 @@
+use std::convert::TryInto;
+use std::io::Write;
+
+use crate::delta::StateMachine;
+use crate::features::collapsed_file::format_count;
+use crate::features::extra_context::get_file_bytes;
+use crate::minusplus::MinusPlus;
+
+const HEXDUMP_ROW_LENGTH: usize = 16;
+
+/// Leading bytes identifying common binary file types, and their MIME types.
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"BM", "image/bmp"),
+    (b"\x00\x00\x01\x00", "image/x-icon"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"\x7fELF", "application/x-executable"),
+    (b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+    (b"\xce\xfa\xed\xfe", "application/x-mach-binary"),
+    (b"MZ", "application/vnd.microsoft.portable-executable"),
+    (b"\x00asm", "application/wasm"),
+    (b"SQLite format 3\x00", "application/vnd.sqlite3"),
+];
+
+/// Return the MIME type of a file with the given content.
+fn mime_type(content: &[u8]) -> &'static str {
+    if content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    MAGIC_NUMBERS
+        .iter()
+        .find(|(magic, _)| content.starts_with(magic))
+        .map_or("application/octet-stream", |(_, mime_type)| mime_type)
+}
+
+fn u16_be(bytes: &[u8], i: usize) -> Option<usize> {
+    Some(u16::from_be_bytes(bytes.get(i..i + 2)?.try_into().ok()?) as usize)
+}
+
+fn u16_le(bytes: &[u8], i: usize) -> Option<usize> {
+    Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?) as usize)
+}
+
+fn u24_le(bytes: &[u8], i: usize) -> Option<usize> {
+    Some(u16_le(bytes, i)? | (*bytes.get(i + 2)? as usize) << 16)
+}
+
+fn u32_be(bytes: &[u8], i: usize) -> Option<usize> {
+    Some(u32::from_be_bytes(bytes.get(i..i + 4)?.try_into().ok()?) as usize)
+}
+
+fn i32_le(bytes: &[u8], i: usize) -> Option<usize> {
+    Some(i32::from_le_bytes(bytes.get(i..i + 4)?.try_into().ok()?).unsigned_abs() as usize)
+}
+
+/// Return the width and height of an image, from its header.
+fn image_dimensions(content: &[u8], mime_type: &str) -> Option<(usize, usize)> {
+    match mime_type {
+        "image/png" if content.get(12..16)? == b"IHDR" => {
+            Some((u32_be(content, 16)?, u32_be(content, 20)?))
+        }
+        "image/gif" => Some((u16_le(content, 6)?, u16_le(content, 8)?)),
+        "image/bmp" => Some((i32_le(content, 18)?, i32_le(content, 22)?)),
+        "image/jpeg" => jpeg_dimensions(content),
+        "image/webp" => match content.get(12..16)? {
+            b"VP8X" => Some((u24_le(content, 24)? + 1, u24_le(content, 27)? + 1)),
+            b"VP8L" => {
+                let bits = u24_le(content, 21)? | (*content.get(24)? as usize) << 24;
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8 " => Some((u16_le(content, 26)? & 0x3fff, u16_le(content, 28)? & 0x3fff)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Return the dimensions from the first start-of-frame segment of a JPEG file.
+fn jpeg_dimensions(content: &[u8]) -> Option<(usize, usize)> {
+    let mut i = 2;
+    loop {
+        if *content.get(i)? != 0xff {
+            return None;
+        }
+        match *content.get(i + 1)? {
+            // Fill byte
+            0xff => i += 1,
+            // Markers without a segment
+            0x01 | 0xd0..=0xd9 => i += 2,
+            // Start of frame, other than DHT, JPG and DAC markers
+            0xc0..=0xcf if ![0xc4, 0xc8, 0xcc].contains(content.get(i + 1)?) => {
+                return Some((u16_be(content, i + 7)?, u16_be(content, i + 5)?));
+            }
+            _ => i += 2 + u16_be(content, i + 2)?,
+        }
+    }
+}
+
+/// Return a description of a file such as "image/png 640×480".
+fn describe(content: &[u8]) -> String {
+    let mime_type = mime_type(content);
+    match image_dimensions(content, mime_type) {
+        Some((width, height)) => format!("{mime_type} {width}×{height}"),
+        None => mime_type.to_string(),
+    }
+}
+
+/// Return lines describing the type and size of the old and new versions of a binary file. If only
+/// one version is given, the lines are labeled with its side, unless the file was added or removed.
+fn summary_lines(contents: &MinusPlus<Option<Vec<u8>>>, is_added_or_removed: bool) -> Vec<String> {
+    match (&contents.minus, &contents.plus) {
+        (Some(minus), Some(plus)) => {
+            let (minus_description, plus_description) = (describe(minus), describe(plus));
+            let size_change = plus.len() as i64 - minus.len() as i64;
+            vec![
+                if minus_description == plus_description {
+                    minus_description
+                } else {
+                    format!("{minus_description} → {plus_description}")
+                },
+                format!(
+                    "{} → {} bytes ({}{})",
+                    format_count(minus.len()),
+                    format_count(plus.len()),
+                    if size_change < 0 { "-" } else { "+" },
+                    format_count(size_change.unsigned_abs() as usize)
+                ),
+            ]
+        }
+        (Some(content), None) | (None, Some(content)) => {
+            let label = match (is_added_or_removed, &contents.minus) {
+                (true, _) => "",
+                (false, Some(_)) => "old: ",
+                (false, None) => "new: ",
+            };
+            vec![
+                format!("{label}{}", describe(content)),
+                format!("{label}{} bytes", format_count(content.len())),
+            ]
+        }
+        (None, None) => Vec::new(),
+    }
+}
+
+/// Return a hexdump-style row, e.g. "00000010  00 61 73 6d  |.asm|".
+fn hexdump_row(offset: usize, bytes: &[u8]) -> String {
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ascii = bytes
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect::<String>();
+    format!(
+        "{offset:08x}  {hex:width$}  |{ascii}|",
+        width = HEXDUMP_ROW_LENGTH * 3 - 1
+    )
+}
+
+/// Return the hexdump rows which differ between the old and new versions of a file, as pairs of
+/// old and new rows.
+fn hexdump_diff(minus: &[u8], plus: &[u8]) -> Vec<(Option<String>, Option<String>)> {
+    let minus_rows = minus.chunks(HEXDUMP_ROW_LENGTH);
+    let plus_rows = plus.chunks(HEXDUMP_ROW_LENGTH);
+    let n_rows = minus_rows.len().max(plus_rows.len());
+    let (mut minus_rows, mut plus_rows) = (minus_rows.fuse(), plus_rows.fuse());
+    (0..n_rows)
+        .filter_map(|i| {
+            let offset = i * HEXDUMP_ROW_LENGTH;
+            match (minus_rows.next(), plus_rows.next()) {
+                (minus_row, plus_row) if minus_row == plus_row => None,
+                (minus_row, plus_row) => Some((
+                    minus_row.map(|row| hexdump_row(offset, row)),
+                    plus_row.map(|row| hexdump_row(offset, row)),
+                )),
+            }
+        })
+        .collect()
+}
+
+impl StateMachine<'_> {
+    /// Read the old and new versions of the current binary file, if a summary or hexdump is
+    /// requested. Absent versions (for added or removed files) are empty.
+    pub fn get_binary_file_contents(&self) -> Option<MinusPlus<Option<Vec<u8>>>> {
+        if !(self.config.binary_summary || self.config.binary_hexdump_max_size > 0)
+            || self.config.color_only
+            || !self.should_display_file()
+        {
+            return None;
+        }
+        let contents = get_file_bytes(
+            &self.minus_file,
+            &self.plus_file,
+            self.minus_blob_id.as_deref(),
+            self.plus_blob_id.as_deref(),
+            self.config,
+        );
+        let is_known =
+            |file: &str, content: &Option<Vec<u8>>| file != "/dev/null" && content.is_some();
+        if !is_known(&self.minus_file, &contents.minus)
+            && !is_known(&self.plus_file, &contents.plus)
+        {
+            return None;
+        }
+        Some(contents)
+    }
+
+    /// Write the file header, followed by the summary and hexdump of the changes to the binary file.
+    pub fn write_binary_file_summary(
+        &mut self,
+        contents: MinusPlus<Option<Vec<u8>>>,
+    ) -> std::io::Result<()> {
+        self.handle_pending_line_with_diff_name()?;
+        self.handled_diff_header_header_line_file_pair
+            .clone_from(&self.current_file_pair);
+        // Absent versions of added or removed files are not described.
+        let present =
+            |file: &str, content: Option<Vec<u8>>| content.filter(|_| file != "/dev/null");
+        let described_contents = MinusPlus::new(
+            present(&self.minus_file, contents.minus.clone()),
+            present(&self.plus_file, contents.plus.clone()),
+        );
+        if self.config.binary_summary {
+            let is_added_or_removed =
+                self.minus_file == "/dev/null" || self.plus_file == "/dev/null";
+            for line in summary_lines(&described_contents, is_added_or_removed) {
+                writeln!(
+                    self.painter.writer,
+                    "{}",
+                    self.config.binary_summary_style.paint(line)
+                )?;
+            }
+        }
+        if let (Some(minus), Some(plus)) = (&contents.minus, &contents.plus) {
+            if minus.len().max(plus.len()) <= self.config.binary_hexdump_max_size {
+                writeln!(self.painter.writer)?;
+                for (minus_row, plus_row) in hexdump_diff(minus, plus) {
+                    if let Some(row) = minus_row {
+                        let line = format!("-{row}");
+                        writeln!(
+                            self.painter.writer,
+                            "{}",
+                            self.config.minus_style.paint(line)
+                        )?;
+                    }
+                    if let Some(row) = plus_row {
+                        let line = format!("+{row}");
+                        writeln!(
+                            self.painter.writer,
+                            "{}",
+                            self.config.plus_style.paint(line)
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_640_480: &[u8] =
+        b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x02\x80\x00\x00\x01\xe0\x08\x06\x00\x00\x00";
+    const PNG_800_600: &[u8] =
+        b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x03\x20\x00\x00\x02\x58\x08\x06\x00\x00\x00";
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(PNG_640_480), "image/png 640×480");
+        assert_eq!(describe(b"GIF89a\x0a\x00\x14\x00"), "image/gif 10×20");
+        assert_eq!(
+            describe(b"\xff\xd8\xff\xe0\x00\x04\x00\x00\xff\xc0\x00\x11\x08\x00\x1e\x00\x28"),
+            "image/jpeg 40×30"
+        );
+        assert_eq!(describe(b"%PDF-1.7"), "application/pdf");
+        assert_eq!(describe(b"\x00\x01\x02"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        assert_eq!(
+            summary_lines(
+                &MinusPlus::new(
+                    Some(PNG_640_480.to_vec()),
+                    Some([PNG_800_600, &[0; 2000]].concat())
+                ),
+                false
+            ),
+            vec![
+                "image/png 640×480 → image/png 800×600",
+                "29 → 2,029 bytes (+2,000)"
+            ]
+        );
+        assert_eq!(
+            summary_lines(&MinusPlus::new(None, Some(PNG_640_480.to_vec())), true),
+            vec!["image/png 640×480", "29 bytes"]
+        );
+        // The other version of a modified file is unavailable.
+        assert_eq!(
+            summary_lines(&MinusPlus::new(None, Some(PNG_640_480.to_vec())), false),
+            vec!["new: image/png 640×480", "new: 29 bytes"]
+        );
+        assert_eq!(
+            summary_lines(&MinusPlus::new(Some(PNG_640_480.to_vec()), None), false),
+            vec!["old: image/png 640×480", "old: 29 bytes"]
+        );
+    }
+
+    #[test]
+    fn test_hexdump_diff() {
+        let minus = b"0123456789abcdef0123456789abcdef01";
+        let plus = b"0123456789abcdef0123456789abcdeF";
+        assert_eq!(
+            hexdump_diff(minus, plus),
+            vec![
+                (
+                    Some(
+                        "00000010  30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66  |0123456789abcdef|"
+                            .to_string()
+                    ),
+                    Some(
+                        "00000010  30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 46  |0123456789abcdeF|"
+                            .to_string()
+                    ),
+                ),
+                (
+                    Some(
+                        "00000020  30 31                                            |01|"
+                            .to_string()
+                    ),
+                    None
+                ),
+            ]
+        );
+    }
+}
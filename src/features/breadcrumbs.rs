@@ -0,0 +1,299 @@
+use lazy_static::lazy_static;
+use syntect::parsing::{ParseState, Scope, ScopeStack, ScopeStackOp, SyntaxReference, SyntaxSet};
+
+use crate::config::HunkHeaderBreadcrumbs;
+use crate::delta::StateMachine;
+use crate::features::extra_context::get_plus_file_content;
+
+lazy_static! {
+    /// Scopes of the names of definitions which are displayed in breadcrumbs.
+    static ref DEFINITION_NAME_SCOPES: Vec<Scope> = [
+        "entity.name.class",
+        "entity.name.enum",
+        "entity.name.function",
+        "entity.name.impl",
+        "entity.name.interface",
+        "entity.name.module",
+        "entity.name.namespace",
+        "entity.name.struct",
+        "entity.name.trait",
+        "entity.name.type",
+    ]
+    .iter()
+    .map(|s| Scope::new(s).unwrap())
+    .collect();
+    /// Scopes of keywords introducing a definition, e.g. `storage.type.function.rust` for `fn`.
+    static ref DEFINITION_KEYWORD_SCOPES: Vec<Scope> = ["keyword.declaration", "storage.type"]
+        .iter()
+        .map(|s| Scope::new(s).unwrap())
+        .collect();
+}
+
+/// A definition (function, class, etc) enclosing the lines parsed so far.
+#[derive(Debug)]
+struct Definition {
+    label: String,
+    indentation: usize,
+    // The scopes enclosing the definition name. The definition ends at the first non-blank line
+    // which is outside these scopes and is not indented more than the line containing the name.
+    scopes: Vec<Scope>,
+}
+
+/// The new version of a file, parsed up to the start of the current hunk, in order to display the
+/// definitions enclosing each hunk.
+#[derive(Debug)]
+pub struct Breadcrumbs {
+    lines: Vec<String>,
+    parse_state: ParseState,
+    scope_stack: ScopeStack,
+    next_line_number: usize,
+    definitions: Vec<Definition>,
+}
+
+impl Breadcrumbs {
+    pub fn new(content: &str, syntax: &SyntaxReference) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+            parse_state: ParseState::new(syntax),
+            scope_stack: ScopeStack::new(),
+            next_line_number: 1,
+            definitions: Vec::new(),
+        }
+    }
+
+    /// Return the breadcrumb for the hunk starting at plus-file line `line_number`, i.e. the
+    /// definitions enclosing the lines preceding it, or None if the file could not be parsed.
+    pub fn get(&mut self, line_number: usize, syntax_set: &SyntaxSet) -> Option<String> {
+        while self.next_line_number < line_number.min(self.lines.len() + 1) {
+            let line = std::mem::take(&mut self.lines[self.next_line_number - 1]);
+            self.parse_line(&line, syntax_set)?;
+            self.next_line_number += 1;
+        }
+        Some(
+            self.definitions
+                .iter()
+                .map(|definition| definition.label.as_str())
+                .collect::<Vec<_>>()
+                .join(" > "),
+        )
+    }
+
+    fn parse_line(&mut self, line: &str, syntax_set: &SyntaxSet) -> Option<()> {
+        let indentation = line.len() - line.trim_start().len();
+        let is_blank = line.trim().is_empty();
+        if !is_blank {
+            // E.g. a dedented line following a Python function body.
+            self.close_definitions(indentation, self.definitions.len());
+        }
+        let n_definitions = self.definitions.len();
+
+        let line = format!("{line}\n");
+        let ops = self.parse_state.parse_line(&line, syntax_set).ok()?;
+        let mut keyword = None;
+        // The start of the definition name, and the scope stack depth of its scope.
+        let mut name_start: Option<(usize, usize)> = None;
+        let mut name_end = None;
+        for (i, op) in ops {
+            self.scope_stack.apply(&op).ok()?;
+            let scopes = self.scope_stack.as_slice();
+            match (name_start, &op) {
+                (None, ScopeStackOp::Push(scope)) if name_end.is_none() => {
+                    if DEFINITION_NAME_SCOPES
+                        .iter()
+                        .any(|s| s.is_prefix_of(*scope))
+                    {
+                        name_start = Some((i, scopes.len()));
+                        self.definitions.push(Definition {
+                            label: String::new(),
+                            indentation,
+                            scopes: scopes[..scopes.len() - 1].to_vec(),
+                        });
+                    } else if scope.len() > 3
+                        && DEFINITION_KEYWORD_SCOPES
+                            .iter()
+                            .any(|s| s.is_prefix_of(*scope))
+                    {
+                        keyword = line[i..]
+                            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            .next()
+                            .filter(|word| !word.is_empty());
+                    }
+                }
+                (Some((start, depth)), _) if scopes.len() < depth => {
+                    name_end = Some(i);
+                    name_start = None;
+                    self.set_label(keyword, &line[start..i]);
+                }
+                _ => {}
+            }
+        }
+        if let Some((start, _)) = name_start {
+            self.set_label(keyword, &line[start..]);
+        }
+        if !is_blank {
+            // E.g. the closing brace of a Rust function body.
+            self.close_definitions(indentation, n_definitions);
+        }
+        Some(())
+    }
+
+    /// Close the first `n_definitions` definitions, and those nested in them, if they end at a
+    /// line with the given indentation and the current scopes.
+    fn close_definitions(&mut self, indentation: usize, n_definitions: usize) {
+        let scopes = self.scope_stack.as_slice();
+        if let Some(i) = self.definitions[..n_definitions]
+            .iter()
+            .position(|definition| {
+                indentation <= definition.indentation && !scopes.starts_with(&definition.scopes)
+            })
+        {
+            self.definitions.truncate(i);
+        }
+    }
+
+    fn set_label(&mut self, keyword: Option<&str>, name: &str) {
+        let name = name.trim();
+        if let Some(definition) = self.definitions.last_mut() {
+            definition.label = match keyword {
+                Some(keyword) => format!("{keyword} {name}"),
+                None => name.to_string(),
+            };
+        }
+    }
+}
+
+impl StateMachine<'_> {
+    /// Read the new version of the current file, if breadcrumbs are requested.
+    pub fn start_breadcrumbs(&mut self) {
+        self.breadcrumbs = None;
+        if matches!(
+            self.config.hunk_header_breadcrumbs,
+            HunkHeaderBreadcrumbs::Off
+        ) || self.config.color_only
+            || self.plus_file == "/dev/null"
+        {
+            return;
+        }
+        self.breadcrumbs =
+            get_plus_file_content(&self.plus_file, self.plus_blob_id.as_deref(), self.config)
+                .map(|content| Breadcrumbs::new(&content, self.painter.syntax));
+    }
+
+    /// Return the hunk header code fragment, with the breadcrumb for the hunk starting at plus-file
+    /// line `line_number` in place of, or after, the code fragment found by git.
+    pub fn code_fragment_with_breadcrumb(
+        &mut self,
+        code_fragment: &str,
+        line_number: usize,
+    ) -> String {
+        let syntax_set = &self.config.syntax_set;
+        let Some(breadcrumb) = self
+            .breadcrumbs
+            .as_mut()
+            .and_then(|breadcrumbs| breadcrumbs.get(line_number, syntax_set))
+        else {
+            return code_fragment.to_string();
+        };
+        match (&self.config.hunk_header_breadcrumbs, breadcrumb.is_empty()) {
+            (HunkHeaderBreadcrumbs::Replace, true) => String::new(),
+            (HunkHeaderBreadcrumbs::Replace, false) => format!(" {breadcrumb}"),
+            (HunkHeaderBreadcrumbs::Append, true) | (HunkHeaderBreadcrumbs::Off, _) => {
+                code_fragment.to_string()
+            }
+            (HunkHeaderBreadcrumbs::Append, false) if code_fragment.trim().is_empty() => {
+                format!(" {breadcrumb}")
+            }
+            (HunkHeaderBreadcrumbs::Append, false) => {
+                format!("{} ({breadcrumb})", code_fragment.trim_end())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::integration_test_utils::make_config_from_args;
+
+    fn get_breadcrumbs(content: &str, extension: &str, line_numbers: &[usize]) -> Vec<String> {
+        let config = make_config_from_args(&[]);
+        let syntax = config
+            .syntax_set
+            .find_syntax_by_extension(extension)
+            .unwrap();
+        let mut breadcrumbs = Breadcrumbs::new(content, syntax);
+        line_numbers
+            .iter()
+            .map(|&n| breadcrumbs.get(n, &config.syntax_set).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_breadcrumbs_rust() {
+        let content = "\
+impl<'a> Painter<'a> {
+    pub fn paint_lines(
+        &mut self,
+    ) -> bool
+    where
+        T: Write,
+    {
+        true
+    }
+
+    fn emit(&mut self) {}
+}
+
+struct S {
+    a: u8,
+}
+";
+        assert_eq!(
+            get_breadcrumbs(content, "rs", &[1, 3, 8, 10, 12, 13, 16]),
+            vec![
+                "",
+                "impl Painter > fn paint_lines",
+                "impl Painter > fn paint_lines",
+                "impl Painter",
+                "impl Painter > fn emit",
+                "",
+                "struct S",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breadcrumbs_python() {
+        let content = "\
+class A(B):
+    def f(self,
+          x):
+
+        return x
+
+    y = 1
+
+def g():
+    pass
+";
+        assert_eq!(
+            get_breadcrumbs(content, "py", &[4, 6, 8, 10]),
+            vec!["class A > def f", "class A > def f", "class A", "def g"]
+        );
+    }
+
+    #[test]
+    fn test_breadcrumbs_javascript() {
+        let content = "\
+class A {
+  method() {
+    return 1;
+  }
+}
+";
+        assert_eq!(
+            get_breadcrumbs(content, "js", &[3, 5]),
+            vec!["class A > method", "class A"]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::Write;
+
+use git2::AttrValue;
+
+use crate::delta::{State, StateMachine};
+
+/// A file whose hunks are replaced by a single line stating the number of changed lines.
+#[derive(Debug, Default)]
+pub struct CollapsedFile {
+    n_changed_lines: usize,
+}
+
+impl CollapsedFile {
+    pub fn count_line(&mut self, state: &State) {
+        if matches!(state, State::HunkMinus(_, _) | State::HunkPlus(_, _)) {
+            self.n_changed_lines += 1;
+        }
+    }
+}
+
+impl StateMachine<'_> {
+    /// Collapse the hunks of the current file if its path matches --collapse-files, or if it is
+    /// marked as generated in .gitattributes and --collapse-generated-files is set.
+    pub fn start_collapsed_file(&mut self) {
+        self.collapsed_file = None;
+        if self.config.color_only {
+            return;
+        }
+        let path = if self.plus_file != "/dev/null" {
+            &self.plus_file
+        } else {
+            &self.minus_file
+        };
+        let is_generated = || {
+            self.config.git_config().is_some_and(|git_config| {
+                matches!(
+                    git_config.get_attribute(path, "linguist-generated"),
+                    Some(AttrValue::True | AttrValue::String("true"))
+                ) || matches!(
+                    git_config.get_attribute(path, "diff"),
+                    Some(AttrValue::False)
+                )
+            })
+        };
+        if self.config.collapse_files.is_match(path)
+            || self.config.collapse_generated_files && is_generated()
+        {
+            self.collapsed_file = Some(CollapsedFile::default());
+        }
+    }
+
+    /// Write the line replacing the hunks of the current file, if it is collapsed.
+    pub fn write_collapsed_file_line(&mut self) -> std::io::Result<()> {
+        let Some(collapsed_file) = self.collapsed_file.take() else {
+            return Ok(());
+        };
+        if collapsed_file.n_changed_lines == 0 {
+            return Ok(());
+        }
+        let n = collapsed_file.n_changed_lines;
+        let description = format!(
+            "⋯ {} {} changed (collapsed)",
+            format_count(n),
+            if n == 1 { "line" } else { "lines" }
+        );
+        writeln!(self.painter.writer)?;
+        writeln!(
+            self.painter.writer,
+            "{}",
+            self.config.collapsed_file_style.paint(description)
+        )
+    }
+}
+
+/// Format `n` with comma thousands separators, e.g. "1,204".
+pub fn format_count(n: usize) -> String {
+    let digits = n.to_string();
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1204), "1,204");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+}
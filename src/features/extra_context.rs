@@ -0,0 +1,258 @@
+use std::ops::Range;
+
+use crate::config::Config;
+use crate::delta::{DiffType, State, StateMachine};
+use crate::minusplus::MinusPlus;
+use crate::paint::prepare;
+use crate::utils;
+
+/// The content of the new version of a file, from which extra context lines are displayed around
+/// its hunks.
+#[derive(Debug)]
+pub struct ExtraContext {
+    lines: Vec<String>,
+    n_lines: usize,
+    // The plus-file line number following the last line of the previous hunk.
+    previous_hunk_end: Option<usize>,
+}
+
+/// The extra context lines to display before a hunk, as ranges of plus-file line numbers.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HunkContext {
+    /// The hunk is merged with the previous hunk: display the lines between them, and no header.
+    Merged(Range<usize>),
+    /// Display lines after the previous hunk, then the hunk header, then lines before the hunk.
+    Separate {
+        after_previous_hunk: Range<usize>,
+        before_hunk: Range<usize>,
+    },
+}
+
+impl ExtraContext {
+    pub fn new(content: &str, n_lines: usize) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+            n_lines,
+            previous_hunk_end: None,
+        }
+    }
+
+    /// Return the extra context lines to display before the hunk with the given header line
+    /// numbers, or None if extra context cannot be displayed for it.
+    pub fn hunk_context(
+        &mut self,
+        line_numbers_and_hunk_lengths: &[(usize, usize)],
+    ) -> Option<HunkContext> {
+        let &[_, (_, plus_hunk_length)] = line_numbers_and_hunk_lengths else {
+            return None;
+        };
+        let hunk_start = hunk_start(line_numbers_and_hunk_lengths);
+        let hunk_end = hunk_start + plus_hunk_length;
+        if hunk_end > self.lines.len() + 1 {
+            // The content does not correspond to the diff.
+            return None;
+        }
+        let hunk_context = match self.previous_hunk_end {
+            Some(previous_hunk_end) if hunk_start <= previous_hunk_end + 2 * self.n_lines => {
+                HunkContext::Merged(previous_hunk_end..hunk_start)
+            }
+            _ => HunkContext::Separate {
+                after_previous_hunk: self.after_previous_hunk(),
+                before_hunk: hunk_start.saturating_sub(self.n_lines).max(1)..hunk_start,
+            },
+        };
+        self.previous_hunk_end = Some(hunk_end);
+        Some(hunk_context)
+    }
+
+    /// Return the extra context lines to display after the previous hunk.
+    pub fn after_previous_hunk(&self) -> Range<usize> {
+        match self.previous_hunk_end {
+            Some(end) => end..(end + self.n_lines).min(self.lines.len() + 1),
+            None => 0..0,
+        }
+    }
+
+    fn line(&self, line_number: usize) -> &str {
+        &self.lines[line_number - 1]
+    }
+}
+
+/// Return the plus-file line number of the first line of a hunk.
+pub fn hunk_start(line_numbers_and_hunk_lengths: &[(usize, usize)]) -> usize {
+    match line_numbers_and_hunk_lengths.last() {
+        // An empty range starts at the line preceding it.
+        Some(&(line_number, 0)) => line_number + 1,
+        Some(&(line_number, _)) => line_number,
+        None => 0,
+    }
+}
+
+/// Return hunk header line numbers and lengths extended to include `n` lines preceding the hunk.
+/// On each side, the hunk is extended by at most the number of lines preceding it.
+pub fn extend_hunk_start(
+    line_numbers_and_hunk_lengths: &[(usize, usize)],
+    n: usize,
+) -> Vec<(usize, usize)> {
+    line_numbers_and_hunk_lengths
+        .iter()
+        .map(|&(line_number, hunk_length)| {
+            let n = n.min(hunk_start(&[(line_number, hunk_length)]).saturating_sub(1));
+            match (n, hunk_length) {
+                (0, _) => (line_number, hunk_length),
+                (n, 0) => (line_number + 1 - n, n),
+                (n, hunk_length) => (line_number - n, hunk_length + n),
+            }
+        })
+        .collect()
+}
+
+/// Return the content of the new version of the file `path`. Prefer the working tree file, if its
+/// blob id matches `blob_id` (from the diff's "index" line); otherwise look up the blob.
+pub fn get_plus_file_content(path: &str, blob_id: Option<&str>, config: &Config) -> Option<String> {
+    String::from_utf8(get_plus_file_bytes(path, blob_id, config)?).ok()
+}
+
+/// As `get_plus_file_content`, for a file which need not be UTF-8.
+pub fn get_plus_file_bytes(path: &str, blob_id: Option<&str>, config: &Config) -> Option<Vec<u8>> {
+    let worktree_content = utils::path::absolute_path(path, config)
+        .and_then(|path| std::fs::read(path).ok())
+        .filter(|content| match blob_id {
+            Some(blob_id) => git2::Oid::hash_object(git2::ObjectType::Blob, content)
+                .is_ok_and(|oid| oid.to_string().starts_with(blob_id)),
+            None => true,
+        });
+    match (worktree_content, blob_id) {
+        (Some(content), _) => Some(content),
+        (None, Some(blob_id)) => config.git_config()?.get_blob_content(blob_id),
+        (None, None) => None,
+    }
+}
+
+/// Return the old and new versions of the file, if they are available. An absent version (of an
+/// added or removed file) is empty.
+pub fn get_file_bytes(
+    minus_file: &str,
+    plus_file: &str,
+    minus_blob_id: Option<&str>,
+    plus_blob_id: Option<&str>,
+    config: &Config,
+) -> MinusPlus<Option<Vec<u8>>> {
+    // A blob id of zeros denotes an absent file.
+    fn blob_id(blob_id: Option<&str>) -> Option<&str> {
+        blob_id.filter(|id| id.chars().any(|c| c != '0'))
+    }
+    let minus = match (minus_file, blob_id(minus_blob_id)) {
+        ("/dev/null", _) => Some(Vec::new()),
+        (_, Some(blob_id)) => config
+            .git_config()
+            .and_then(|git_config| git_config.get_blob_content(blob_id)),
+        (_, None) => None,
+    };
+    let plus = match plus_file {
+        "/dev/null" => Some(Vec::new()),
+        _ => get_plus_file_bytes(plus_file, blob_id(plus_blob_id), config),
+    };
+    MinusPlus::new(minus, plus)
+}
+
+impl StateMachine<'_> {
+    /// Read the new version of the current file, if extra context is requested.
+    pub fn start_extra_context(&mut self) {
+        self.extra_context = None;
+        if self.config.extra_context == 0
+            || self.config.color_only
+            || self.minus_file == "/dev/null"
+            || self.plus_file == "/dev/null"
+        {
+            return;
+        }
+        self.extra_context =
+            get_plus_file_content(&self.plus_file, self.plus_blob_id.as_deref(), self.config)
+                .map(|content| ExtraContext::new(&content, self.config.extra_context));
+    }
+
+    /// Paint the lines with the given plus-file line numbers as unchanged hunk lines.
+    pub fn paint_extra_context_lines(&mut self, line_numbers: Range<usize>) -> std::io::Result<()> {
+        let Some(extra_context) = &self.extra_context else {
+            return Ok(());
+        };
+        for line_number in line_numbers {
+            let line = prepare(
+                &format!(" {}", extra_context.line(line_number)),
+                1,
+                self.config,
+            );
+            self.painter
+                .paint_zero_line(&line, State::HunkZero(DiffType::Unified, None));
+        }
+        self.painter.emit()
+    }
+
+    /// Paint the extra context lines following the last hunk of the current file.
+    pub fn finish_extra_context(&mut self) -> std::io::Result<()> {
+        if let Some(extra_context) = &self.extra_context {
+            let line_numbers = extra_context.after_previous_hunk();
+            self.painter.paint_buffered_minus_and_plus_lines();
+            self.paint_extra_context_lines(line_numbers)?;
+        }
+        self.extra_context = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunk_context() {
+        let content = (1..=20).map(|n| format!("{n}\n")).collect::<String>();
+        let mut extra_context = ExtraContext::new(&content, 2);
+        assert_eq!(
+            extra_context.hunk_context(&[(4, 3), (4, 4)]),
+            Some(HunkContext::Separate {
+                after_previous_hunk: 0..0,
+                before_hunk: 2..4,
+            })
+        );
+        assert_eq!(
+            extra_context.hunk_context(&[(10, 3), (11, 3)]),
+            Some(HunkContext::Merged(8..11))
+        );
+        assert_eq!(
+            extra_context.hunk_context(&[(17, 2), (19, 2)]),
+            Some(HunkContext::Separate {
+                after_previous_hunk: 14..16,
+                before_hunk: 17..19,
+            })
+        );
+        assert_eq!(extra_context.after_previous_hunk(), 21..21);
+        assert_eq!(extra_context.hunk_context(&[(30, 1), (30, 1)]), None);
+    }
+
+    #[test]
+    fn test_extend_hunk_start() {
+        assert_eq!(
+            extend_hunk_start(&[(4, 3), (4, 4)], 2),
+            vec![(2, 5), (2, 6)]
+        );
+        assert_eq!(
+            extend_hunk_start(&[(4, 0), (5, 2)], 2),
+            vec![(3, 2), (3, 4)]
+        );
+        assert_eq!(
+            extend_hunk_start(&[(4, 3), (4, 4)], 0),
+            vec![(4, 3), (4, 4)]
+        );
+        // Lines were removed before the hunk, so fewer lines precede it on the minus side.
+        assert_eq!(
+            extend_hunk_start(&[(2, 3), (5, 3)], 3),
+            vec![(1, 4), (2, 6)]
+        );
+        assert_eq!(
+            extend_hunk_start(&[(0, 0), (1, 2)], 1),
+            vec![(0, 0), (1, 2)]
+        );
+    }
+}
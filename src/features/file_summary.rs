@@ -0,0 +1,113 @@
+use std::io::Write;
+
+use crate::delta::{State, StateMachine};
+use crate::features::extra_context::get_file_bytes;
+use crate::features::lockfile_summary::{self, Lockfile, LockfileChanges};
+use crate::features::structural_summary::{self, DataFormat, FileContent};
+use crate::handlers::hunk::is_word_diff;
+use crate::minusplus::MinusPlus;
+
+/// The data from which a summary of the changes to a file is computed. The summary is written
+/// after the file header, so the output for the hunks of the file is held back until it is known.
+#[derive(Debug)]
+pub enum FileSummary {
+    Structural(FileContent),
+    Lockfile(LockfileChanges),
+}
+
+impl FileSummary {
+    pub fn start_hunk(&mut self, line_numbers_and_hunk_lengths: &[(usize, usize)]) {
+        match self {
+            FileSummary::Structural(file_content) => {
+                file_content.start_hunk(line_numbers_and_hunk_lengths)
+            }
+            FileSummary::Lockfile(lockfile_changes) => lockfile_changes.start_hunk(),
+        }
+    }
+
+    /// Add a hunk line, as given by the input with its diff prefix.
+    pub fn push_line(&mut self, line: &str, state: &State) {
+        match self {
+            FileSummary::Structural(file_content) => file_content.push_line(line, state),
+            FileSummary::Lockfile(lockfile_changes) => lockfile_changes.push_line(line, state),
+        }
+    }
+}
+
+impl StateMachine<'_> {
+    /// If a summary is requested for the current file, hold back the output for its hunks.
+    pub fn start_file_summary(&mut self) {
+        if self.config.color_only || is_word_diff() {
+            return;
+        }
+        let path = if self.plus_file != "/dev/null" {
+            &self.plus_file
+        } else {
+            &self.minus_file
+        };
+        let file_summary = match Lockfile::from_path(path) {
+            Some(lockfile) if self.config.lockfile_summary => {
+                Some(FileSummary::Lockfile(LockfileChanges::new(lockfile)))
+            }
+            _ if self.config.structural_summary => {
+                DataFormat::from_syntax_name(&self.painter.syntax.name)
+                    .map(|format| FileSummary::Structural(FileContent::new(format)))
+            }
+            _ => None,
+        };
+        if file_summary.is_some() {
+            self.painter.writer.hold();
+            self.file_summary = file_summary;
+        }
+    }
+
+    /// Write the summary of the current file, if any, followed by the held output.
+    pub fn write_file_summary(&mut self) -> std::io::Result<()> {
+        let Some(file_summary) = self.file_summary.take() else {
+            return Ok(());
+        };
+        let held_output = self.painter.writer.release();
+        match file_summary {
+            FileSummary::Structural(file_content) => {
+                let style = self.config.structural_summary_style;
+                let file_bytes = get_file_bytes(
+                    &self.minus_file,
+                    &self.plus_file,
+                    self.minus_blob_id.as_deref(),
+                    self.plus_blob_id.as_deref(),
+                    self.config,
+                );
+                let to_string = |bytes: Option<Vec<u8>>| String::from_utf8(bytes?).ok();
+                let file_contents =
+                    MinusPlus::new(to_string(file_bytes.minus), to_string(file_bytes.plus));
+                let changes = file_content.changes(&file_contents).unwrap_or_default();
+                let max_lines = structural_summary::MAX_SUMMARY_LINES;
+                for change in changes.iter().take(max_lines) {
+                    writeln!(self.painter.writer, "{}", style.paint(change.to_string()))?;
+                }
+                if changes.len() > max_lines {
+                    let n_more = changes.len() - max_lines;
+                    writeln!(
+                        self.painter.writer,
+                        "{}",
+                        style.paint(format!("⋯ {n_more} more changed keys"))
+                    )?;
+                }
+            }
+            FileSummary::Lockfile(lockfile_changes) => {
+                let changes = lockfile_changes.changes();
+                for row in lockfile_summary::format_table(&changes) {
+                    writeln!(
+                        self.painter.writer,
+                        "{}",
+                        self.config.lockfile_summary_style.paint(row)
+                    )?;
+                }
+                if self.config.collapse_lockfile_hunks && !changes.is_empty() {
+                    return Ok(());
+                }
+            }
+        }
+        self.painter.writer.write_all(&held_output)
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
 
 use lazy_static::lazy_static;
@@ -26,6 +27,9 @@ lazy_static! {
     // when more objects are in a repository.
     // Note: pure numbers are filtered out later again.
     static ref COMMIT_HASH_REGEX: Regex = Regex::new(r"\b[0-9a-f]{7,40}\b").unwrap();
+    // E.g. "Co-authored-by: Name <name@example.com>", capturing the email address.
+    static ref TRAILER_EMAIL_REGEX: Regex =
+        Regex::new(r"^\s*[A-Za-z-]+-by:.*<([^<>\s]+@[^<>\s]+)>").unwrap();
 }
 
 pub fn format_commit_line_with_osc8_commit_hyperlink<'a>(
@@ -83,10 +87,83 @@ pub fn format_commit_line_with_osc8_commit_hyperlink<'a>(
     Cow::from(line)
 }
 
-/// Create a file hyperlink, displaying `text`.
+/// Return the file link format opening a file at a line and column in the given editor.
+pub fn editor_file_link_format(editor: &str) -> Option<&'static str> {
+    match editor {
+        "vscode" => Some("vscode://file/{path}:{line}:{column}"),
+        "idea" => Some("idea://open?file={path}&line={line}&column={column}"),
+        "sublime" => Some("subl://open?url=file://{path}&line={line}&column={column}"),
+        "zed" => Some("zed://file{path}:{line}:{column}"),
+        _ => None,
+    }
+}
+
+/// Create hyperlinks in a line of a commit message: to issues referenced in it, to the email
+/// address of a trailer such as Co-authored-by, and to commits.
+pub fn format_commit_message_line_with_osc8_hyperlinks<'a>(
+    line: &'a str,
+    config: &Config,
+) -> Cow<'a, str> {
+    let mut links: Vec<(Range<usize>, String)> = Vec::new();
+    if config.hyperlinks_issue_link_format.is_empty() {
+        if let Some(repo) = config
+            .git_config()
+            .and_then(|git_config| git_config.get_remote_url().as_ref())
+        {
+            for caps in repo.issue_reference_regex().captures_iter(line) {
+                if let Some(url) = repo.format_issue_url(&caps[1]) {
+                    links.push((caps.get(0).unwrap().range(), url));
+                }
+            }
+        }
+    } else {
+        for (regex, url_format) in &config.hyperlinks_issue_link_format {
+            for caps in regex.captures_iter(line) {
+                let m = caps.get(0).unwrap();
+                let issue = caps.get(1).unwrap_or(m).as_str();
+                links.push((m.range(), url_format.replace("{issue}", issue)));
+            }
+        }
+    }
+    if let Some(email) = TRAILER_EMAIL_REGEX
+        .captures(line)
+        .and_then(|caps| caps.get(1))
+    {
+        links.push((email.range(), format!("mailto:{}", email.as_str())));
+    }
+    if links.is_empty() {
+        return format_commit_line_with_osc8_commit_hyperlink(line, config);
+    }
+
+    // Commit hashes are linked in the text between the links, which must not overlap.
+    links.sort_by_key(|(range, _)| range.start);
+    let mut result = String::new();
+    let mut pos = 0;
+    for (range, url) in links {
+        if range.start < pos || range.is_empty() {
+            continue;
+        }
+        result.push_str(&format_commit_line_with_osc8_commit_hyperlink(
+            &line[pos..range.start],
+            config,
+        ));
+        result.push_str(&format_osc8_hyperlink(&url, &line[range.clone()]));
+        pos = range.end;
+    }
+    result.push_str(&format_commit_line_with_osc8_commit_hyperlink(
+        &line[pos..],
+        config,
+    ));
+    Cow::from(result)
+}
+
+/// Create a file hyperlink, displaying `text`. `commit` is the revision of the file linked to by
+/// `{remote_url}`, HEAD if None.
 pub fn format_osc8_file_hyperlink<'a, P>(
     absolute_path: P,
     line_number: Option<usize>,
+    column: Option<usize>,
+    commit: Option<&str>,
     text: &str,
     config: &Config,
 ) -> Cow<'a, str>
@@ -95,17 +172,38 @@ where
     P: std::fmt::Debug,
 {
     debug_assert!(absolute_path.as_ref().is_absolute());
-    let mut url = config
-        .hyperlinks_file_link_format
-        .replace("{path}", &absolute_path.as_ref().to_string_lossy());
+    let mut url = config.hyperlinks_file_link_format.clone();
+    if url.contains("{remote_url}") {
+        // Link to the file in the web interface of the remote repository if possible, otherwise
+        // to the local file.
+        let remote_url =
+            format_remote_file_url(absolute_path.as_ref(), line_number, commit, config)
+                .unwrap_or_else(|| "file://{path}".to_string());
+        url = url.replace("{remote_url}", &remote_url);
+    }
+    url = url.replace("{path}", &absolute_path.as_ref().to_string_lossy());
     if let Some(host) = &config.hostname {
         url = url.replace("{host}", host)
     }
     let n = line_number.unwrap_or(1);
     url = url.replace("{line}", &format!("{n}"));
+    url = url.replace("{column}", &format!("{}", column.unwrap_or(1)));
     Cow::from(format_osc8_hyperlink(&url, text))
 }
 
+fn format_remote_file_url(
+    absolute_path: &Path,
+    line_number: Option<usize>,
+    commit: Option<&str>,
+    config: &Config,
+) -> Option<String> {
+    let git_config = config.git_config()?;
+    let repo = git_config.get_remote_url().as_ref()?;
+    let path = git_config.get_path_relative_to_repo_root(absolute_path)?;
+    let commit = commit.or_else(|| git_config.get_head_commit())?;
+    Some(repo.format_file_url(commit, &path, line_number))
+}
+
 fn format_osc8_hyperlink(url: &str, text: &str) -> String {
     format!(
         "{osc}8;;{url}{st}{text}{osc}8;;{st}",
@@ -136,21 +234,89 @@ pub mod tests {
         let config =
             make_config_from_args(&["--hyperlinks-file-link-format", "file://{path}:{line}"]);
 
-        let result =
-            format_osc8_file_hyperlink("/absolute/path/to/file.rs", Some(42), "file.rs", &config);
+        let result = format_osc8_file_hyperlink(
+            "/absolute/path/to/file.rs",
+            Some(42),
+            None,
+            None,
+            "file.rs",
+            &config,
+        );
         assert_eq!(
             result,
             "\u{1b}]8;;file:///absolute/path/to/file.rs:42\u{1b}\\file.rs\u{1b}]8;;\u{1b}\\",
         );
 
-        let result =
-            format_osc8_file_hyperlink("/absolute/path/to/file.rs", None, "file.rs", &config);
+        let result = format_osc8_file_hyperlink(
+            "/absolute/path/to/file.rs",
+            None,
+            None,
+            None,
+            "file.rs",
+            &config,
+        );
         assert_eq!(
             result,
             "\u{1b}]8;;file:///absolute/path/to/file.rs:1\u{1b}\\file.rs\u{1b}]8;;\u{1b}\\",
         );
     }
 
+    #[test]
+    fn test_file_hyperlink_editor_presets() {
+        let config = make_config_from_args(&["--hyperlinks-editor", "vscode"]);
+        let result = format_osc8_file_hyperlink(
+            "/absolute/path/to/file.rs",
+            Some(42),
+            Some(7),
+            None,
+            "file.rs",
+            &config,
+        );
+        assert_eq!(
+            result,
+            "\u{1b}]8;;vscode://file//absolute/path/to/file.rs:42:7\u{1b}\\file.rs\u{1b}]8;;\u{1b}\\",
+        );
+
+        let config = make_config_from_args(&["--hyperlinks-editor", "idea"]);
+        let result = format_osc8_file_hyperlink(
+            "/absolute/path/to/file.rs",
+            Some(42),
+            None,
+            None,
+            "file.rs",
+            &config,
+        );
+        assert_eq!(
+            result,
+            "\u{1b}]8;;idea://open?file=/absolute/path/to/file.rs&line=42&column=1\u{1b}\\file.rs\u{1b}]8;;\u{1b}\\",
+        );
+
+        for editor in ["sublime", "zed"] {
+            assert!(editor_file_link_format(editor).is_some());
+        }
+        assert_eq!(editor_file_link_format("ed"), None);
+        assert_eq!(editor_file_link_format("file-line"), None);
+    }
+
+    #[test]
+    fn test_file_hyperlink_remote_url_falls_back_to_local_file() {
+        // There is no repository in tests, so the remote URL of the file cannot be determined.
+        let config = make_config_from_args(&["--hyperlinks-file-link-format", "{remote_url}"]);
+
+        let result = format_osc8_file_hyperlink(
+            "/absolute/path/to/file.rs",
+            Some(42),
+            None,
+            Some("1c072856ebf12419378c5098ad543c497197c6da"),
+            "file.rs",
+            &config,
+        );
+        assert_eq!(
+            result,
+            "\u{1b}]8;;file:///absolute/path/to/file.rs\u{1b}\\file.rs\u{1b}]8;;\u{1b}\\",
+        );
+    }
+
     #[test]
     fn test_formatted_hyperlinks() {
         let config = make_config_from_args(&["--hyperlinks-commit-link-format", "HERE:{commit}"]);
@@ -225,6 +391,84 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_commit_message_hyperlinks_to_repo_issues() {
+        let mut config = make_config_from_args(&["--hyperlinks"]);
+        config.git_config = GitConfig::for_testing();
+
+        let line = "    Fix GH-12 (see #34 and a589ff9debaefdd)";
+        let result = format_commit_message_line_with_osc8_hyperlinks(line, &config);
+        assert_eq!(
+            result,
+            "    Fix \u{1b}]8;;https://github.com/dandavison/delta/issues/12\u{1b}\\GH-12\
+            \u{1b}]8;;\u{1b}\\ (see \u{1b}]8;;https://github.com/dandavison/delta/issues/34\
+            \u{1b}\\#34\u{1b}]8;;\u{1b}\\ and \u{1b}]8;;https://github.com/dandavison/delta/\
+            commit/a589ff9debaefdd\u{1b}\\a589ff9debaefdd\u{1b}]8;;\u{1b}\\)",
+        );
+
+        let line = "    Co-authored-by: Ada Lovelace <ada@example.com>";
+        let result = format_commit_message_line_with_osc8_hyperlinks(line, &config);
+        assert_eq!(
+            result,
+            "    Co-authored-by: Ada Lovelace <\u{1b}]8;;mailto:ada@example.com\u{1b}\\\
+            ada@example.com\u{1b}]8;;\u{1b}\\>",
+        );
+    }
+
+    #[test]
+    fn test_commit_message_hyperlinks_with_issue_link_format() {
+        let mut config = make_config_from_args(&[
+            "--hyperlinks",
+            "--hyperlinks-issue-link-format",
+            "JIRA-[0-9]+ => https://jira.example.com/browse/{issue}\n\\bT([0-9]+) => https://tracker/{issue}",
+        ]);
+        config.git_config = GitConfig::for_testing();
+
+        // The configured formats replace the links to the issues of the remote repository.
+        let line = "    Fix JIRA-456, T7 and #34";
+        let result = format_commit_message_line_with_osc8_hyperlinks(line, &config);
+        assert_eq!(
+            result,
+            "    Fix \u{1b}]8;;https://jira.example.com/browse/JIRA-456\u{1b}\\JIRA-456\
+            \u{1b}]8;;\u{1b}\\, \u{1b}]8;;https://tracker/7\u{1b}\\T7\u{1b}]8;;\u{1b}\\ and #34",
+        );
+    }
+
+    #[test]
+    fn test_issue_link_format_with_counted_repetition() {
+        // Commas in a regex do not separate formats.
+        let mut config = make_config_from_args(&[
+            "--hyperlinks",
+            "--hyperlinks-issue-link-format",
+            "[A-Z]{2,5}-[0-9]+ => https://jira.example.com/browse/{issue}\nT{1,2}([0-9]+) => https://tracker/{issue}",
+        ]);
+        config.git_config = GitConfig::for_testing();
+
+        let line = "    Fix AB-1 and T7";
+        let result = format_commit_message_line_with_osc8_hyperlinks(line, &config);
+        assert_eq!(
+            result,
+            "    Fix \u{1b}]8;;https://jira.example.com/browse/AB-1\u{1b}\\AB-1\u{1b}]8;;\u{1b}\\ \
+            and \u{1b}]8;;https://tracker/7\u{1b}\\T7\u{1b}]8;;\u{1b}\\",
+        );
+    }
+
+    #[test]
+    fn test_commit_message_hyperlinks_not_written_to_non_tty() {
+        // As for other lines emitted unaltered, commit message lines are only hyperlinked when
+        // output is going to a tty, which it is not in tests.
+        let result = DeltaTest::with_args(&[
+            "--hyperlinks",
+            "--hyperlinks-issue-link-format",
+            "#([0-9]+) => https://tracker/{issue}",
+        ])
+        .with_input(
+            "commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e\nAuthor: A <a@b.c>\n\n    Fix #12\n",
+        )
+        .expect_raw_contains("\n    Fix #12\n");
+        assert!(!result.raw_output.contains("https://tracker/12"));
+    }
+
     #[test]
     fn test_paths_and_hyperlinks_user_in_repo_root_dir() {
         // Expectations are uninfluenced by git's --relative and delta's relative_paths options.
@@ -0,0 +1,190 @@
+use chrono::{DateTime, FixedOffset, Local, TimeZone};
+
+use crate::ansi::measure_text_width;
+use crate::config::Config;
+use crate::delta::StateMachine;
+use crate::features::OptionValueFunction;
+use crate::format;
+use crate::git_config::BlameCommit;
+use crate::handlers::blame::{format_blame_metadata, BlameLine, BLAME_PLACEHOLDER_REGEX};
+
+pub fn make_feature() -> Vec<(String, OptionValueFunction)> {
+    builtin_feature!([
+        (
+            "inline-blame",
+            bool,
+            None,
+            _opt => true
+        ),
+        ("features", bool, None, _opt => "line-numbers")
+    ])
+}
+
+/// Annotations of the lines of the old version of a file with the commits that last changed them.
+#[derive(Debug, Default)]
+pub struct InlineBlame {
+    annotations: Vec<String>,
+    pub width: usize,
+}
+
+impl InlineBlame {
+    fn new(commits: &[Option<BlameCommit>], config: &Config) -> Self {
+        let format_data = format::parse_line_number_format(
+            &config.inline_blame_format,
+            &BLAME_PLACEHOLDER_REGEX,
+            false,
+        );
+        let annotations: Vec<_> = commits
+            .iter()
+            .map(|commit| format_annotation(&format_data, commit.as_ref(), config))
+            .collect();
+        // The width of an annotation is fixed by the format string, unless it lacks widths.
+        let width = annotations
+            .iter()
+            .map(|annotation| measure_text_width(annotation))
+            .chain(std::iter::once(measure_text_width(&format_annotation(
+                &format_data,
+                None,
+                config,
+            ))))
+            .max()
+            .unwrap_or(0);
+        Self { annotations, width }
+    }
+
+    /// Return the annotation of the line with the given minus-file line number, padded to the
+    /// width of the annotation column.
+    pub fn annotation(&self, line_number: Option<usize>) -> String {
+        let annotation = line_number
+            .and_then(|n| self.annotations.get(n.checked_sub(1)?))
+            .map_or("", String::as_str);
+        format::pad(annotation, self.width, format::Align::Left, None)
+    }
+}
+
+/// Return the annotation of a line last changed by `commit`, or by no commit yet.
+fn format_annotation(
+    format_data: &format::FormatStringData,
+    commit: Option<&BlameCommit>,
+    config: &Config,
+) -> String {
+    let (id, author, time) = match commit {
+        Some(commit) => (
+            commit.id.get(..8).unwrap_or(&commit.id),
+            commit.author.as_str(),
+            to_datetime(commit.time),
+        ),
+        None => ("00000000", "Not Committed Yet", None),
+    };
+    let blame = BlameLine {
+        commit: id,
+        author,
+        time: time.unwrap_or_else(|| Local::now().fixed_offset()),
+        line_number: 0,
+        code: "",
+    };
+    format_blame_metadata(format_data, &blame, config)
+}
+
+fn to_datetime(time: git2::Time) -> Option<DateTime<FixedOffset>> {
+    FixedOffset::east_opt(time.offset_minutes() * 60)?
+        .timestamp_opt(time.seconds(), 0)
+        .single()
+}
+
+/// Return the commit hash in a commit line of git log or git show output.
+pub fn parse_commit_hash(line: &str) -> Option<String> {
+    line.split_whitespace()
+        .find(|word| word.len() >= 7 && word.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_string)
+}
+
+impl StateMachine<'_> {
+    /// Compute the blame annotations of the old version of the current file, if requested.
+    pub fn start_inline_blame(&mut self) {
+        if !self.config.inline_blame {
+            return;
+        }
+        let commits = (self.minus_file != "/dev/null")
+            .then(|| self.config.git_config())
+            .flatten()
+            .and_then(|git_config| {
+                // The old version of a file in a commit is its version in the parent commit.
+                let newest_commit = self.commit.as_ref().map(|commit| format!("{commit}^"));
+                let content = self
+                    .minus_blob_id
+                    .as_deref()
+                    .and_then(|blob_id| git_config.get_blob_content(blob_id));
+                git_config.get_blame(
+                    &self.minus_file,
+                    newest_commit.as_deref(),
+                    content.as_deref(),
+                )
+            })
+            .unwrap_or_default();
+        if let Some(line_numbers_data) = &mut self.painter.line_numbers_data {
+            line_numbers_data.inline_blame = Some(InlineBlame::new(&commits, self.config));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ansi::strip_ansi_codes;
+    use crate::tests::integration_test_utils::{
+        make_config_from_args, make_config_from_args_and_git_config,
+    };
+
+    #[test]
+    fn test_inline_blame_implies_line_numbers() {
+        let config = make_config_from_args(&["--inline-blame"]);
+        assert!(config.inline_blame && config.line_numbers);
+
+        let git_config_path =
+            std::env::temp_dir().join("delta__test_inline_blame_implies_line_numbers.gitconfig");
+        let config = make_config_from_args_and_git_config(
+            &["--inline-blame"],
+            Some(b"[delta]\n    line-numbers = false\n"),
+            git_config_path.to_str(),
+        );
+        assert!(config.inline_blame && config.line_numbers);
+        std::fs::remove_file(git_config_path).unwrap();
+    }
+
+    #[test]
+    fn test_inline_blame_annotations() {
+        let config = make_config_from_args(&[
+            "--inline-blame",
+            "--inline-blame-format",
+            "{commit:<8} {author:<8.7}│",
+            "--inline-blame-style",
+            "raw",
+        ]);
+        let commit = BlameCommit {
+            id: "d3b07384d113edec49eaa6238ad5ff00".to_string(),
+            author: "Ada Lovelace".to_string(),
+            time: git2::Time::new(0, 0),
+        };
+        let inline_blame = InlineBlame::new(&[Some(commit), None], &config);
+        assert_eq!(
+            strip_ansi_codes(&inline_blame.annotation(Some(1))),
+            "d3b07384 Ada Lov │"
+        );
+        assert_eq!(
+            strip_ansi_codes(&inline_blame.annotation(Some(2))),
+            "00000000 Not Com │"
+        );
+        assert_eq!(inline_blame.annotation(Some(3)), " ".repeat(18));
+        assert_eq!(inline_blame.annotation(None), " ".repeat(18));
+    }
+
+    #[test]
+    fn test_parse_commit_hash() {
+        assert_eq!(
+            parse_commit_hash("commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e (HEAD -> main)"),
+            Some("94907c0f136f46dc46ffae2dc92dca9af7eb7c2e".to_string())
+        );
+        assert_eq!(parse_commit_hash("commit"), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::io::Write;
+
+use crate::delta::{State, StateMachine};
+use crate::features::collapsed_file::format_count;
+use crate::handlers::hunk::is_word_diff;
+use crate::minusplus::MinusPlus;
+
+/// The maximum number of lines of a Git LFS pointer file: the version line, the oid and size
+/// lines, and a few extension lines.
+const MAX_POINTER_LINES: usize = 8;
+
+const OID_PREFIX_LENGTH: usize = 8;
+
+/// The contents of a Git LFS pointer file, see
+/// https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md
+#[derive(Debug, PartialEq, Eq)]
+struct LfsPointer {
+    oid: String,
+    size: usize,
+}
+
+impl LfsPointer {
+    fn parse(lines: &[String]) -> Option<Self> {
+        let (version, keys) = lines.split_first()?;
+        if !version.starts_with("version https://git-lfs.github.com/spec/") {
+            return None;
+        }
+        let (mut oid, mut size) = (None, None);
+        for line in keys {
+            match line.split_once(' ')? {
+                ("oid", value) => oid = Some(value.to_string()),
+                ("size", value) => size = value.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Return the object id without its hash method, shortened, e.g. "4d7a2146".
+    fn short_oid(&self) -> &str {
+        let hash = self
+            .oid
+            .split_once(':')
+            .map_or(&*self.oid, |(_, hash)| hash);
+        hash.get(..OID_PREFIX_LENGTH).unwrap_or(hash)
+    }
+
+    fn hash_method(&self) -> &str {
+        self.oid.split_once(':').map_or("", |(method, _)| method)
+    }
+}
+
+/// The lines of a hunk which may be the diff of a Git LFS pointer file. The output for the hunk
+/// is held back until it is known whether it is.
+#[derive(Debug)]
+pub struct LfsPointerHunk {
+    lines: MinusPlus<Vec<String>>,
+}
+
+impl LfsPointerHunk {
+    /// Add a hunk line, as given by the input with its diff prefix.
+    pub fn push_line(&mut self, line: &str, state: &State) {
+        let content = line.get(1..).unwrap_or("").to_string();
+        match state {
+            State::HunkMinus(_, _) => self.lines.minus.push(content),
+            State::HunkPlus(_, _) => self.lines.plus.push(content),
+            State::HunkZero(_, _) if !line.starts_with('\\') => {
+                self.lines.minus.push(content.clone());
+                self.lines.plus.push(content);
+            }
+            _ => {}
+        }
+    }
+
+    /// Return the old and new pointers, or None if either side is neither empty nor a pointer.
+    fn pointers(&self) -> Option<MinusPlus<Option<LfsPointer>>> {
+        let parse = |lines: &Vec<String>| match lines.as_slice() {
+            [] => Some(None),
+            lines => LfsPointer::parse(lines).map(Some),
+        };
+        let pointers = MinusPlus::new(parse(&self.lines.minus)?, parse(&self.lines.plus)?);
+        if pointers.minus.is_none() && pointers.plus.is_none() {
+            return None;
+        }
+        Some(pointers)
+    }
+}
+
+/// Return the line describing the change to a Git LFS object.
+fn summary_line(pointers: &MinusPlus<Option<LfsPointer>>) -> String {
+    let describe = |event: &str, pointer: &LfsPointer| {
+        format!(
+            "Git LFS object {event}: {} bytes ({}:{})",
+            format_count(pointer.size),
+            pointer.hash_method(),
+            pointer.short_oid()
+        )
+    };
+    match (&pointers.minus, &pointers.plus) {
+        (Some(minus), Some(plus)) => format!(
+            "Git LFS object changed: {} → {} bytes ({}:{} → {})",
+            format_count(minus.size),
+            format_count(plus.size),
+            plus.hash_method(),
+            minus.short_oid(),
+            plus.short_oid()
+        ),
+        (None, Some(plus)) => describe("added", plus),
+        (Some(minus), None) => describe("removed", minus),
+        (None, None) => String::new(),
+    }
+}
+
+impl StateMachine<'_> {
+    /// If the hunk starting at the current hunk header line may be the diff of a Git LFS pointer
+    /// file, hold back its output. A pointer file is small, so its diff is a single hunk starting
+    /// at the first line.
+    pub fn start_lfs_pointer_hunk(
+        &mut self,
+        line_numbers_and_hunk_lengths: &[(usize, usize)],
+    ) -> std::io::Result<()> {
+        // A second hunk means that the file is not a pointer file.
+        if self.lfs_pointer_hunk.take().is_some() {
+            let held_output = self.painter.writer.release();
+            self.painter.writer.write_all(&held_output)?;
+        }
+        if !self.config.lfs_summary
+            || self.config.color_only
+            || is_word_diff()
+            || self.file_summary.is_some()
+            || self.collapsed_file.is_some()
+        {
+            return Ok(());
+        }
+        let is_small_first_hunk = |&(line_number, hunk_length): &(usize, usize)| {
+            line_number <= 1 && hunk_length <= MAX_POINTER_LINES
+        };
+        if line_numbers_and_hunk_lengths.len() == 2
+            && line_numbers_and_hunk_lengths
+                .iter()
+                .all(is_small_first_hunk)
+        {
+            self.painter.writer.hold();
+            self.lfs_pointer_hunk = Some(LfsPointerHunk {
+                lines: MinusPlus::new(Vec::new(), Vec::new()),
+            });
+        }
+        Ok(())
+    }
+
+    /// If the current file is a Git LFS pointer file, write a summary of the change to the LFS
+    /// object in place of its diff. Otherwise write the held output.
+    pub fn write_lfs_pointer_summary(&mut self) -> std::io::Result<()> {
+        let Some(lfs_pointer_hunk) = self.lfs_pointer_hunk.take() else {
+            return Ok(());
+        };
+        self.painter.paint_buffered_minus_and_plus_lines();
+        self.painter.emit()?;
+        let held_output = self.painter.writer.release();
+        match lfs_pointer_hunk.pointers() {
+            Some(pointers) => {
+                writeln!(self.painter.writer)?;
+                writeln!(
+                    self.painter.writer,
+                    "{}",
+                    self.config.lfs_summary_style.paint(summary_line(&pointers))
+                )
+            }
+            None => self.painter.writer.write_all(&held_output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn pointer(oid: &str, size: usize) -> LfsPointer {
+        LfsPointer {
+            oid: oid.to_string(),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_parse_lfs_pointer() {
+        assert_eq!(
+            LfsPointer::parse(&lines(&[
+                "version https://git-lfs.github.com/spec/v1",
+                "oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393",
+                "size 12345",
+            ])),
+            Some(pointer(
+                "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393",
+                12345
+            ))
+        );
+        assert_eq!(
+            LfsPointer::parse(&lines(&["fn main() {", "oid sha256:4d7a", "size 1"])),
+            None
+        );
+        assert_eq!(
+            LfsPointer::parse(&lines(&[
+                "version https://git-lfs.github.com/spec/v1",
+                "oid sha256:4d7a",
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let minus = pointer("sha256:4d7a214614ab2935", 1234);
+        let plus = pointer("sha256:9f86d081884c7d65", 56789);
+        assert_eq!(
+            summary_line(&MinusPlus::new(Some(minus), Some(plus))),
+            "Git LFS object changed: 1,234 → 56,789 bytes (sha256:4d7a2146 → 9f86d081)"
+        );
+        let plus = pointer("sha256:9f86d081884c7d65", 56789);
+        assert_eq!(
+            summary_line(&MinusPlus::new(None, Some(plus))),
+            "Git LFS object added: 56,789 bytes (sha256:9f86d081)"
+        );
+    }
+}
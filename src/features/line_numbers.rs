@@ -7,6 +7,7 @@ use crate::color::ColorMode::*;
 use crate::config;
 use crate::delta::State;
 use crate::features::hyperlinks;
+use crate::features::inline_blame::InlineBlame;
 use crate::features::side_by_side::ansifill::{self, ODD_PAD_CHAR};
 use crate::features::side_by_side::{Left, PanelSide, Right};
 use crate::features::OptionValueFunction;
@@ -114,14 +115,21 @@ pub fn format_and_paint_line_numbers<'a>(
 ) -> Vec<ansi_term::ANSIGenericString<'a, str>> {
     let mut formatted_numbers = Vec::new();
 
-    let (emit_left, emit_right) = match (config.side_by_side, side_by_side_panel) {
-        (false, _) => (true, true),
-        (true, Some(Left)) => (true, false),
-        (true, Some(Right)) => (false, true),
-        (true, None) => unreachable!(),
+    // Lines may be displayed in unified layout in side-by-side mode, see --side-by-side-mode.
+    let (emit_left, emit_right) = match side_by_side_panel {
+        None => (true, true),
+        Some(Left) => (true, false),
+        Some(Right) => (false, true),
     };
 
     if emit_left {
+        if let Some(inline_blame) = &line_numbers_data.inline_blame {
+            formatted_numbers.push(
+                config
+                    .inline_blame_style
+                    .paint(inline_blame.annotation(line_numbers[Minus])),
+            );
+        }
         formatted_numbers.extend(format_and_paint_line_number_field(
             line_numbers_data,
             Minus,
@@ -145,7 +153,7 @@ pub fn format_and_paint_line_numbers<'a>(
 
 lazy_static! {
     static ref LINE_NUMBERS_PLACEHOLDER_REGEX: Regex =
-        format::make_placeholder_regex(&["nm", "np"]);
+        format::make_placeholder_regex(&["nm", "np", "hn", "hm", "hp", "rm", "rp", "prefix"]);
 }
 
 #[derive(Default, Debug)]
@@ -154,6 +162,17 @@ pub struct LineNumbersData<'a> {
     pub line_number: MinusPlus<usize>,
     pub hunk_max_line_number_width: usize,
     pub plus_file: String,
+    // The commit whose diff is being displayed, if any, for hyperlinks to the remote repository.
+    pub commit: Option<String>,
+    // The number of the current hunk within the current file, starting at 1.
+    pub hunk_number: usize,
+    pub hunk_start: MinusPlus<usize>,
+    // The line numbers of the first changed line of the current hunk, once it has been seen. Only
+    // tracked if the format strings display numbers relative to it.
+    pub first_change: Option<MinusPlus<usize>>,
+    pub uses_first_change: bool,
+    // Displayed to the left of the left column, see --inline-blame.
+    pub inline_blame: Option<InlineBlame>,
 }
 
 pub type SideBySideLineWidth = MinusPlus<usize>;
@@ -166,23 +185,36 @@ impl<'a> LineNumbersData<'a> {
         use_full_width: ansifill::UseFullPanelWidth,
     ) -> LineNumbersData<'a> {
         let insert_center_space_on_odd_width = use_full_width.pad_width();
-        Self {
-            format_data: MinusPlus::new(
-                format::parse_line_number_format(
-                    &format[Left],
-                    &LINE_NUMBERS_PLACEHOLDER_REGEX,
-                    false,
-                ),
-                format::parse_line_number_format(
-                    &format[Right],
-                    &LINE_NUMBERS_PLACEHOLDER_REGEX,
-                    insert_center_space_on_odd_width,
-                ),
+        let format_data = MinusPlus::new(
+            format::parse_line_number_format(&format[Left], &LINE_NUMBERS_PLACEHOLDER_REGEX, false),
+            format::parse_line_number_format(
+                &format[Right],
+                &LINE_NUMBERS_PLACEHOLDER_REGEX,
+                insert_center_space_on_odd_width,
             ),
+        );
+        let uses_first_change = [&format_data[Left], &format_data[Right]]
+            .iter()
+            .flat_map(|format_data| format_data.iter())
+            .any(|placeholder| {
+                matches!(
+                    placeholder.placeholder,
+                    Some(Placeholder::RelativeMinus | Placeholder::RelativePlus)
+                )
+            });
+        Self {
+            format_data,
+            uses_first_change,
             ..Self::default()
         }
     }
 
+    /// Initialize line number data for a file, before its first hunk.
+    pub fn initialize_file(&mut self, commit: Option<String>) {
+        self.hunk_number = 0;
+        self.commit = commit;
+    }
+
     /// Initialize line number data for a hunk.
     pub fn initialize_hunk(&mut self, line_numbers: &[(usize, usize)], plus_file: String) {
         // Typically, line_numbers has length 2: an entry for the minus file, and one for the plus
@@ -193,6 +225,24 @@ impl<'a> LineNumbersData<'a> {
         self.hunk_max_line_number_width =
             1 + (hunk_max_line_number as f64).log10().floor() as usize;
         self.plus_file = plus_file;
+        self.hunk_number += 1;
+        self.hunk_start = self.line_number.clone();
+        self.first_change = None;
+    }
+
+    /// Whether lines must wait to be painted until the first changed line of the hunk is seen,
+    /// since their numbers are relative to it.
+    pub fn awaits_first_change(&self) -> bool {
+        self.uses_first_change && self.first_change.is_none()
+    }
+
+    /// Record the first changed line of the hunk, which follows the given number of unchanged
+    /// lines whose numbers have not been painted yet.
+    pub fn set_first_change(&mut self, n_preceding_zero_lines: usize) {
+        self.first_change = Some(MinusPlus::new(
+            self.line_number[Minus] + n_preceding_zero_lines,
+            self.line_number[Plus] + n_preceding_zero_lines,
+        ));
     }
 
     pub fn empty_for_sbs(use_full_width: ansifill::UseFullPanelWidth) -> LineNumbersData<'a> {
@@ -233,8 +283,12 @@ impl<'a> LineNumbersData<'a> {
                 })
                 .unwrap_or(0)
         };
+        let inline_blame_width = self
+            .inline_blame
+            .as_ref()
+            .map_or(0, |inline_blame| inline_blame.width);
         MinusPlus::new(
-            format_data_width(&self.format_data[Left]),
+            inline_blame_width + format_data_width(&self.format_data[Left]),
             format_data_width(&self.format_data[Right]),
         )
     }
@@ -248,7 +302,7 @@ fn format_and_paint_line_number_field<'a>(
     line_numbers: &MinusPlus<Option<usize>>,
     config: &config::Config,
 ) -> Vec<ansi_term::ANSIGenericString<'a, str>> {
-    let min_field_width = line_numbers_data.hunk_max_line_number_width;
+    let hunk_max_line_number_width = line_numbers_data.hunk_max_line_number_width;
 
     let format_data = &line_numbers_data.format_data[side];
     let plus_file = &line_numbers_data.plus_file;
@@ -259,6 +313,11 @@ fn format_and_paint_line_number_field<'a>(
     for placeholder in format_data {
         ansi_strings.push(style.paint(placeholder.prefix.as_str()));
 
+        let min_field_width = if placeholder.placeholder == Some(Placeholder::DiffPrefix) {
+            1
+        } else {
+            hunk_max_line_number_width
+        };
         let width = if let Some(placeholder_width) = placeholder.width {
             max(placeholder_width, min_field_width)
         } else {
@@ -274,6 +333,7 @@ fn format_and_paint_line_number_field<'a>(
                     width,
                     placeholder.precision,
                     None,
+                    None,
                     config,
                 )))
             }
@@ -284,9 +344,73 @@ fn format_and_paint_line_number_field<'a>(
                     width,
                     placeholder.precision,
                     Some(plus_file),
+                    line_numbers_data.commit.as_deref(),
                     config,
                 )))
             }
+            Some(Placeholder::HunkNumber) => ansi_strings.push(
+                style.paint(format_line_number(
+                    line_numbers[Minus]
+                        .or(line_numbers[Plus])
+                        .map(|_| line_numbers_data.hunk_number),
+                    alignment_spec,
+                    width,
+                    placeholder.precision,
+                    None,
+                    None,
+                    config,
+                )),
+            ),
+            Some(Placeholder::HunkOffsetMinus | Placeholder::HunkOffsetPlus) => {
+                let side = if placeholder.placeholder == Some(Placeholder::HunkOffsetMinus) {
+                    Minus
+                } else {
+                    Plus
+                };
+                ansi_strings.push(styles[side].paint(format_line_number(
+                    line_numbers[side].map(|n| n + 1 - line_numbers_data.hunk_start[side]),
+                    alignment_spec,
+                    width,
+                    placeholder.precision,
+                    None,
+                    None,
+                    config,
+                )))
+            }
+            Some(Placeholder::RelativeMinus | Placeholder::RelativePlus) => {
+                let side = if placeholder.placeholder == Some(Placeholder::RelativeMinus) {
+                    Minus
+                } else {
+                    Plus
+                };
+                let first_change = line_numbers_data
+                    .first_change
+                    .as_ref()
+                    .map_or(line_numbers_data.hunk_start[side], |first_change| {
+                        first_change[side]
+                    });
+                let relative_line_number = line_numbers[side]
+                    .map(|n| format_relative_line_number(n as isize - first_change as isize));
+                ansi_strings.push(styles[side].paint(format_field(
+                    relative_line_number,
+                    alignment_spec,
+                    width,
+                    placeholder.precision,
+                )))
+            }
+            Some(Placeholder::DiffPrefix) => {
+                let prefix = match (line_numbers[Minus], line_numbers[Plus]) {
+                    (Some(_), None) => "-",
+                    (None, Some(_)) => "+",
+                    _ => " ",
+                };
+                ansi_strings.push(style.paint(format_field(
+                    Some(prefix.to_string()),
+                    alignment_spec,
+                    width,
+                    placeholder.precision,
+                )))
+            }
             None => {}
             _ => unreachable!("Invalid placeholder"),
         }
@@ -296,6 +420,28 @@ fn format_and_paint_line_number_field<'a>(
     ansi_strings
 }
 
+/// Return a signed line number, e.g. "-2", "0" or "+3".
+fn format_relative_line_number(n: isize) -> String {
+    if n > 0 {
+        format!("+{n}")
+    } else {
+        n.to_string()
+    }
+}
+
+/// Return a field formatted according to `alignment` and `width`, or blank if absent.
+fn format_field(
+    value: Option<String>,
+    alignment: Align,
+    width: usize,
+    precision: Option<usize>,
+) -> String {
+    match value {
+        Some(value) => format::pad(value, width, alignment, precision),
+        None => " ".repeat(width),
+    }
+}
+
 /// Return line number formatted according to `alignment` and `width`.
 fn format_line_number(
     line_number: Option<usize>,
@@ -303,16 +449,22 @@ fn format_line_number(
     width: usize,
     precision: Option<usize>,
     plus_file: Option<&str>,
+    commit: Option<&str>,
     config: &config::Config,
 ) -> String {
     let pad = |n| format::pad(n, width, alignment, precision);
     match (line_number, config.hyperlinks, plus_file) {
         (None, _, _) => " ".repeat(width),
         (Some(n), true, Some(file)) => match utils::path::absolute_path(file, config) {
-            Some(absolute_path) => {
-                hyperlinks::format_osc8_file_hyperlink(absolute_path, line_number, &pad(n), config)
-                    .to_string()
-            }
+            Some(absolute_path) => hyperlinks::format_osc8_file_hyperlink(
+                absolute_path,
+                line_number,
+                None,
+                commit,
+                &pad(n),
+                config,
+            )
+            .to_string(),
             None => pad(n),
         },
         (Some(n), _, _) => pad(n),
@@ -727,6 +879,31 @@ pub mod tests {
         assert_eq!(lines.next().unwrap(), "         ⋮  2 │bb = 2");
     }
 
+    #[test]
+    fn test_hunk_relative_placeholders() {
+        let config = make_config_from_args(&[
+            "--line-numbers",
+            "--line-numbers-left-format",
+            "{hn}:{hm}{prefix}",
+            "--line-numbers-right-format",
+            "{rp:>3}│",
+        ]);
+        let output = run_delta(TWO_LINE_DIFFS, &config);
+        let output = strip_ansi_codes(&output);
+        let lines: Vec<_> = output.lines().filter(|line| line.contains(" = ")).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "1:1  -1│a = 1",
+                "1:2-   │b = 2",
+                "1: +  0│bb = 2",
+                " 2 : 1   -1│a = 3",
+                " 2 : 2 -   │b = 4",
+                " 2 :   +  0│bb = 4",
+            ]
+        );
+    }
+
     #[test]
     fn test_five_digit_line_number() {
         let config = make_config_from_args(&["--line-numbers"]);
@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::delta::{DiffType, State};
+use crate::minusplus::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lockfile {
+    Cargo,
+    Npm,
+    Go,
+}
+
+impl Lockfile {
+    /// Recognize a lockfile by its file name.
+    pub fn from_path(path: &str) -> Option<Self> {
+        match Path::new(path).file_name()?.to_str()? {
+            "Cargo.lock" => Some(Lockfile::Cargo),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Lockfile::Npm),
+            "go.sum" => Some(Lockfile::Go),
+            _ => None,
+        }
+    }
+}
+
+lazy_static! {
+    static ref CARGO_NAME_REGEX: Regex = Regex::new(r#"^name = "([^"]+)"$"#).unwrap();
+    static ref CARGO_VERSION_REGEX: Regex = Regex::new(r#"^version = "([^"]+)"$"#).unwrap();
+    static ref NPM_NAME_REGEX: Regex = Regex::new(r#"^\s*"([^"]+)": \{$"#).unwrap();
+    static ref NPM_VERSION_REGEX: Regex = Regex::new(r#"^\s*"version": "([^"]+)",?$"#).unwrap();
+    static ref GO_SUM_REGEX: Regex = Regex::new(r"^(\S+) (\S+?)(?:/go\.mod)? \S+$").unwrap();
+}
+
+/// The package versions present in the removed and added lines of a lockfile diff.
+#[derive(Debug)]
+pub struct LockfileChanges {
+    lockfile: Lockfile,
+    versions: BTreeMap<String, MinusPlus<BTreeSet<String>>>,
+    // The package whose entry the previous line on each side belongs to.
+    current_package: MinusPlus<Option<String>>,
+}
+
+impl LockfileChanges {
+    pub fn new(lockfile: Lockfile) -> Self {
+        Self {
+            lockfile,
+            versions: BTreeMap::new(),
+            current_package: MinusPlus::default(),
+        }
+    }
+
+    pub fn start_hunk(&mut self) {
+        self.current_package = MinusPlus::default();
+    }
+
+    /// Add a hunk line, as given by the input with its diff prefix.
+    pub fn push_line(&mut self, line: &str, state: &State) {
+        let sides: &[MinusPlusIndex] = match state {
+            State::HunkZero(DiffType::Unified, _) => &[Minus, Plus],
+            State::HunkMinus(DiffType::Unified, _) => &[Minus],
+            State::HunkPlus(DiffType::Unified, _) => &[Plus],
+            _ => return,
+        };
+        let content = line.get(1..).unwrap_or("");
+        for side in sides {
+            self.push_content(content, *side);
+        }
+    }
+
+    fn push_content(&mut self, content: &str, side: MinusPlusIndex) {
+        let (name_regex, version_regex) = match self.lockfile {
+            Lockfile::Cargo => (&*CARGO_NAME_REGEX, &*CARGO_VERSION_REGEX),
+            Lockfile::Npm => (&*NPM_NAME_REGEX, &*NPM_VERSION_REGEX),
+            Lockfile::Go => {
+                if let Some(caps) = GO_SUM_REGEX.captures(content) {
+                    self.add_version(&caps[1], &caps[2], side);
+                }
+                return;
+            }
+        };
+        if let Some(caps) = name_regex.captures(content) {
+            // package-lock.json v2 keys are paths such as "node_modules/a/node_modules/b".
+            let name = caps[1].rsplit("node_modules/").next().unwrap_or_default();
+            self.current_package[side] = Some(name.to_string()).filter(|name| !name.is_empty());
+        } else if let Some(caps) = version_regex.captures(content) {
+            if let Some(name) = self.current_package[side].take() {
+                self.add_version(&name, &caps[1], side);
+            }
+        }
+    }
+
+    fn add_version(&mut self, name: &str, version: &str, side: MinusPlusIndex) {
+        self.versions.entry(name.to_string()).or_default()[side].insert(version.to_string());
+    }
+
+    /// Return the package changes, ordered by package name. Versions present on both sides (from
+    /// unchanged lines) are not changes.
+    pub fn changes(&self) -> Vec<PackageChange> {
+        let mut changes = Vec::new();
+        for (name, versions) in &self.versions {
+            let removed: Vec<_> = versions.minus.difference(&versions.plus).collect();
+            let added: Vec<_> = versions.plus.difference(&versions.minus).collect();
+            match (removed.as_slice(), added.as_slice()) {
+                (&[from], &[to]) => changes.push(PackageChange::Changed(
+                    name.clone(),
+                    from.clone(),
+                    to.clone(),
+                )),
+                _ => {
+                    for version in removed {
+                        changes.push(PackageChange::Removed(name.clone(), version.clone()));
+                    }
+                    for version in added {
+                        changes.push(PackageChange::Added(name.clone(), version.clone()));
+                    }
+                }
+            }
+        }
+        changes
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PackageChange {
+    Added(String, String),
+    Removed(String, String),
+    Changed(String, String, String),
+}
+
+impl PackageChange {
+    fn name(&self) -> &str {
+        match self {
+            PackageChange::Added(name, _)
+            | PackageChange::Removed(name, _)
+            | PackageChange::Changed(name, _, _) => name,
+        }
+    }
+}
+
+/// Format package changes as the rows of a table with aligned columns.
+pub fn format_table(changes: &[PackageChange]) -> Vec<String> {
+    let name_width = changes
+        .iter()
+        .map(|change| change.name().chars().count())
+        .max()
+        .unwrap_or(0);
+    changes
+        .iter()
+        .map(|change| {
+            let (marker, versions) = match change {
+                PackageChange::Added(_, version) => ('+', version.clone()),
+                PackageChange::Removed(_, version) => ('-', version.clone()),
+                PackageChange::Changed(_, from, to) => ('~', format!("{from} → {to}")),
+            };
+            format!("{marker} {:name_width$}  {versions}", change.name())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lockfile_changes(lockfile: Lockfile, hunk: &str) -> Vec<String> {
+        let mut changes = LockfileChanges::new(lockfile);
+        changes.start_hunk();
+        for line in hunk.lines() {
+            let state = match line.chars().next() {
+                Some('-') => State::HunkMinus(DiffType::Unified, None),
+                Some('+') => State::HunkPlus(DiffType::Unified, None),
+                _ => State::HunkZero(DiffType::Unified, None),
+            };
+            changes.push_line(line, &state);
+        }
+        format_table(&changes.changes())
+    }
+
+    #[test]
+    fn test_lockfile_from_path() {
+        assert_eq!(Lockfile::from_path("Cargo.lock"), Some(Lockfile::Cargo));
+        assert_eq!(
+            Lockfile::from_path("web/package-lock.json"),
+            Some(Lockfile::Npm)
+        );
+        assert_eq!(Lockfile::from_path("go.sum"), Some(Lockfile::Go));
+        assert_eq!(Lockfile::from_path("Cargo.toml"), None);
+    }
+
+    #[test]
+    fn test_cargo_lock_changes() {
+        let hunk = r#"
+ [[package]]
+ name = "regex"
+-version = "1.7.1"
++version = "1.8.0"
+ source = "registry+https://github.com/rust-lang/crates.io-index"
+ [[package]]
+-name = "left-pad"
+-version = "1.0.0"
++name = "serde"
++version = "1.0.200"
+"#;
+        assert_eq!(
+            lockfile_changes(Lockfile::Cargo, hunk),
+            vec![
+                "- left-pad  1.0.0",
+                "~ regex     1.7.1 → 1.8.0",
+                "+ serde     1.0.200",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_package_lock_json_changes() {
+        let hunk = r#"
+     "node_modules/a/node_modules/left-pad": {
+-      "version": "1.0.0",
++      "version": "1.3.0",
+       "resolved": "https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz",
+     },
+     "node_modules/unchanged": {
+       "version": "2.0.0",
+"#;
+        assert_eq!(
+            lockfile_changes(Lockfile::Npm, hunk),
+            vec!["~ left-pad  1.0.0 → 1.3.0"]
+        );
+    }
+
+    #[test]
+    fn test_go_sum_changes() {
+        let hunk = "\
+-golang.org/x/text v0.3.7 h1:olpwvP2KacW1ZWvsR7uQhoyTYvKAupfQrRGBFM352Gk=
+-golang.org/x/text v0.3.7/go.mod h1:u+2+/6zg+i71rQMx5EYifcz6MCKuco9NR6JIITiCfzQ=
++golang.org/x/text v0.14.0 h1:ScX5w1eTa3QqT8oi6+ziP7dTV1S2+ALU0bI+0zXKWiQ=
++golang.org/x/text v0.14.0/go.mod h1:18ZOQIKpY8NJVqYksKHtTdi31H5itFRjB5/qKTNYzSU=
++golang.org/x/sys v0.1.0/go.mod h1:oPkhp1MJrh7nUepCBck5+mAzfO9JrbApNNgaTdGDITg=
+";
+        assert_eq!(
+            lockfile_changes(Lockfile::Go, hunk),
+            vec![
+                "+ golang.org/x/sys   v0.1.0",
+                "~ golang.org/x/text  v0.3.7 → v0.14.0",
+            ]
+        );
+    }
+}
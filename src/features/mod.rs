@@ -42,6 +42,10 @@ pub fn make_builtin_features() -> HashMap<String, BuiltinFeature> {
             "hyperlinks".to_string(),
             hyperlinks::make_feature().into_iter().collect(),
         ),
+        (
+            "inline-blame".to_string(),
+            inline_blame::make_feature().into_iter().collect(),
+        ),
         (
             "line-numbers".to_string(),
             line_numbers::make_feature().into_iter().collect(),
@@ -79,14 +83,24 @@ macro_rules! builtin_feature {
     }
 }
 
+pub mod binary_summary;
+pub mod breadcrumbs;
+pub mod collapsed_file;
 pub mod color_only;
 pub mod diff_highlight;
 pub mod diff_so_fancy;
+pub mod extra_context;
+pub mod file_summary;
 pub mod hyperlinks;
+pub mod inline_blame;
+pub mod lfs_pointer;
 pub mod line_numbers;
+pub mod lockfile_summary;
 pub mod navigate;
 pub mod raw;
 pub mod side_by_side;
+pub mod structural_summary;
+pub mod toc;
 
 #[cfg(test)]
 pub mod tests {
@@ -29,33 +29,60 @@ pub fn make_feature() -> Vec<(String, OptionValueFunction)> {
     ])
 }
 
-// Construct the regexp used by less for paging, if --show-themes or --navigate is enabled.
+/// The kinds of stop points of diff navigation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavigateLevel {
+    Commit,
+    File,
+    Hunk,
+    Conflict,
+}
+
+// Construct the regexp used by less for paging, if --show-themes or --navigate is enabled. There is
+// none if every label of the chosen levels is empty.
+#[allow(clippy::too_many_arguments)]
 pub fn make_navigate_regex(
     show_themes: bool,
+    levels: &[NavigateLevel],
     file_modified_label: &str,
     file_added_label: &str,
     file_removed_label: &str,
     file_renamed_label: &str,
     hunk_label: &str,
-) -> String {
+    merge_conflict_begin_symbol: &str,
+) -> Option<String> {
     if show_themes {
-        "^Theme:".to_string()
+        Some("^Theme:".to_string())
     } else {
-        let optional_regexp = |find: &str| {
-            if !find.is_empty() {
-                format!("|{}", regex::escape(find))
-            } else {
-                "".to_string()
-            }
-        };
-        format!(
-            "^(commit{}{}{}{}{})",
-            optional_regexp(file_added_label),
-            optional_regexp(file_removed_label),
-            optional_regexp(file_renamed_label),
-            optional_regexp(file_modified_label),
-            optional_regexp(hunk_label),
-        )
+        let mut alternatives = Vec::new();
+        if levels.contains(&NavigateLevel::Commit) {
+            alternatives.push("commit");
+        }
+        if levels.contains(&NavigateLevel::File) {
+            alternatives.extend([
+                file_added_label,
+                file_removed_label,
+                file_renamed_label,
+                file_modified_label,
+            ]);
+        }
+        if levels.contains(&NavigateLevel::Hunk) {
+            alternatives.push(hunk_label);
+        }
+        // A merge conflict starts with a bar made of this symbol.
+        if levels.contains(&NavigateLevel::Conflict) {
+            alternatives.push(merge_conflict_begin_symbol);
+        }
+        let alternatives: Vec<_> = alternatives
+            .iter()
+            .filter(|find| !find.is_empty())
+            .map(|find| regex::escape(find))
+            .collect();
+        if alternatives.is_empty() {
+            None
+        } else {
+            Some(format!("^({})", alternatives.join("|")))
+        }
     }
 }
 
@@ -162,6 +189,8 @@ fn get_less_hist_file() -> Option<PathBuf> {
 mod tests {
     use std::fs::remove_file;
 
+    use super::*;
+
     use crate::tests::integration_test_utils;
 
     #[test]
@@ -286,4 +315,48 @@ mod tests {
 
         remove_file(git_config_path).unwrap();
     }
+
+    #[test]
+    fn test_navigate_regex_levels() {
+        let make_regex = |levels: &[NavigateLevel]| {
+            make_navigate_regex(false, levels, "Δ", "added:", "removed:", "", "•", "▼")
+        };
+        assert_eq!(
+            make_regex(&[
+                NavigateLevel::Commit,
+                NavigateLevel::File,
+                NavigateLevel::Hunk
+            ]),
+            Some("^(commit|added:|removed:|Δ|•)".to_string())
+        );
+        assert_eq!(make_regex(&[NavigateLevel::Hunk]), Some("^(•)".to_string()));
+        assert_eq!(
+            make_regex(&[NavigateLevel::Hunk, NavigateLevel::Conflict]),
+            Some("^(•|▼)".to_string())
+        );
+        // The file renamed label is empty.
+        assert_eq!(
+            make_navigate_regex(false, &[NavigateLevel::Hunk], "", "", "", "", "", ""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_navigate_levels_option() {
+        let config = integration_test_utils::make_config_from_args(&[
+            "--navigate",
+            "--navigate-levels",
+            "file,conflict",
+        ]);
+        assert_eq!(
+            config.navigate_regex.as_deref(),
+            Some("^(added:|removed:|renamed:|Δ|▼)")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid value for --navigate-levels: ''")]
+    fn test_empty_navigate_levels_option_is_rejected_without_navigate() {
+        integration_test_utils::make_config_from_args(&["--navigate-levels", ""]);
+    }
 }
@@ -246,7 +246,7 @@ pub fn paint_zero_lines_side_by_side<'a>(
     for (line_index, ((syntax_sections, diff_sections), state)) in syntax_style_sections
         .into_iter()
         .zip_eq(diff_style_sections.iter())
-        .zip_eq(states.into_iter())
+        .zip_eq(states)
         .enumerate()
     {
         for panel_side in &[Left, Right] {
@@ -717,6 +717,41 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_side_by_side_mode_auto_min_width() {
+        let result = DeltaTest::with_args(&[
+            "--side-by-side",
+            "--side-by-side-mode=auto",
+            "--side-by-side-min-width=41",
+            "--width",
+            "40",
+        ])
+        .with_input(ONE_MINUS_ONE_PLUS_LINE_DIFF)
+        .skip_header();
+        assert_snapshot!(result, @r###"
+        │  1 ││  1 │a = 1
+        │  2 ││    │b = 2
+        │    ││  2 │bb = 2
+        "###);
+    }
+
+    #[test]
+    fn test_side_by_side_mode_auto_long_lines() {
+        let result = DeltaTest::with_args(&[
+            "--side-by-side",
+            "--side-by-side-mode=auto",
+            "--width",
+            "28",
+            "--line-fill-method=spaces",
+        ])
+        .with_input(TWO_MINUS_LINES_DIFF)
+        .skip_header();
+        assert_snapshot!(result, @r###"
+        │  1 ││    │a = 1
+        │  2 ││    │b = 23456
+        "###);
+    }
+
     #[test]
     fn test_two_minus_lines_unicode_truncated() {
         DeltaTest::with_args(&[
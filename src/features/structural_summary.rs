@@ -0,0 +1,367 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use crate::delta::{DiffType, State};
+use crate::minusplus::*;
+
+/// The maximum number of changed key paths listed for one file.
+pub const MAX_SUMMARY_LINES: usize = 20;
+
+/// Values whose JSON representation is longer than this are abbreviated in the summary.
+const MAX_VALUE_WIDTH: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataFormat {
+    Json,
+    Toml,
+}
+
+impl DataFormat {
+    /// Return the data format of files highlighted with the syntax named `syntax_name`.
+    pub fn from_syntax_name(syntax_name: &str) -> Option<Self> {
+        match syntax_name {
+            "JSON" => Some(DataFormat::Json),
+            "TOML" => Some(DataFormat::Toml),
+            _ => None,
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<Value> {
+        if text.trim().is_empty() {
+            // An added or removed file.
+            return Some(Value::Null);
+        }
+        match self {
+            DataFormat::Json => serde_json::from_str(text).ok(),
+            DataFormat::Toml => toml::from_str(text).ok(),
+        }
+    }
+}
+
+/// The old and new content of a file, as far as it is present in the hunks of the diff.
+#[derive(Debug)]
+pub struct FileContent {
+    format: DataFormat,
+    text: MinusPlus<String>,
+    // The line numbers at which the next hunk must start for the content to be complete.
+    next_line_number: MinusPlus<usize>,
+    is_complete: bool,
+}
+
+impl FileContent {
+    pub fn new(format: DataFormat) -> Self {
+        Self {
+            format,
+            text: MinusPlus::default(),
+            next_line_number: MinusPlus::new(1, 1),
+            is_complete: true,
+        }
+    }
+
+    /// Record the line ranges of a new hunk. Content which is not covered by the hunks cannot be
+    /// summarized, since key paths depend on the lines preceding a change.
+    pub fn start_hunk(&mut self, line_numbers_and_hunk_lengths: &[(usize, usize)]) {
+        match line_numbers_and_hunk_lengths {
+            &[minus, plus] => {
+                for (side, (line_number, hunk_length)) in [(Minus, minus), (Plus, plus)] {
+                    // An empty range starts at the line preceding it.
+                    let start = if hunk_length == 0 {
+                        line_number + 1
+                    } else {
+                        line_number
+                    };
+                    if start != self.next_line_number[side] {
+                        self.is_complete = false;
+                    }
+                    self.next_line_number[side] = start + hunk_length;
+                }
+            }
+            _ => self.is_complete = false,
+        }
+    }
+
+    /// Add a hunk line, as given by the input with its diff prefix.
+    pub fn push_line(&mut self, line: &str, state: &State) {
+        if line.starts_with('\\') {
+            // "\ No newline at end of file"
+            return;
+        }
+        let sides: &[MinusPlusIndex] = match state {
+            State::HunkZero(DiffType::Unified, _) => &[Minus, Plus],
+            State::HunkMinus(DiffType::Unified, _) => &[Minus],
+            State::HunkPlus(DiffType::Unified, _) => &[Plus],
+            _ => {
+                self.is_complete = false;
+                return;
+            }
+        };
+        let content = line.get(1..).unwrap_or("");
+        for side in sides {
+            self.text[*side].push_str(content);
+            self.text[*side].push('\n');
+        }
+    }
+
+    /// Return the changed key paths, or None if they cannot be known or the content does not
+    /// parse. They are computed from the full old and new versions of the file, if both are
+    /// available. Otherwise they are computed from the hunks, provided these cover the file from
+    /// its first line to its end, as known from the new version. If the hunks show the new version
+    /// to be empty, as for a removed file, all the keys of the old version are removed.
+    pub fn changes(&self, file_contents: &MinusPlus<Option<String>>) -> Option<Vec<Change>> {
+        match (&file_contents.minus, &file_contents.plus) {
+            (Some(minus), Some(plus)) => diff_texts(self.format, minus, plus),
+            (Some(minus), None) if self.is_complete && self.next_line_number.plus == 1 => {
+                diff_texts(self.format, minus, "")
+            }
+            (None, Some(plus))
+                if self.is_complete && self.next_line_number.plus == plus.lines().count() + 1 =>
+            {
+                diff_texts(self.format, &self.text.minus, &self.text.plus)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Return the changed key paths between two versions of a file, or None if either does not parse.
+fn diff_texts(format: DataFormat, minus: &str, plus: &str) -> Option<Vec<Change>> {
+    let minus = format.parse(minus)?;
+    let plus = format.parse(plus)?;
+    let mut changes = Vec::new();
+    diff_values(&mut Vec::new(), &minus, &plus, &mut changes);
+    Some(changes)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Modified(String, String, String),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Added(path) => write!(f, "+ {path}"),
+            Change::Removed(path) => write!(f, "- {path}"),
+            Change::Modified(path, minus, plus) => write!(f, "{path}: {minus} → {plus}"),
+        }
+    }
+}
+
+enum PathElement<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+fn format_path(path: &[PathElement]) -> String {
+    if path.is_empty() {
+        return ".".to_string();
+    }
+    let mut s = String::new();
+    for element in path {
+        match element {
+            PathElement::Key(key) => {
+                if !s.is_empty() {
+                    s.push('.');
+                }
+                if key.is_empty() || key.contains(['.', ' ', '[', '"']) {
+                    s.push_str(&Value::from(*key).to_string());
+                } else {
+                    s.push_str(key);
+                }
+            }
+            PathElement::Index(i) => s.push_str(&format!("[{i}]")),
+        }
+    }
+    s
+}
+
+fn format_value(value: &Value) -> String {
+    let s = value.to_string();
+    if s.chars().count() <= MAX_VALUE_WIDTH {
+        s
+    } else {
+        match value {
+            Value::Object(_) => "{…}".to_string(),
+            Value::Array(_) => "[…]".to_string(),
+            _ => format!(
+                "{}…",
+                s.chars().take(MAX_VALUE_WIDTH - 1).collect::<String>()
+            ),
+        }
+    }
+}
+
+fn diff_values<'a>(
+    path: &mut Vec<PathElement<'a>>,
+    minus: &'a Value,
+    plus: &'a Value,
+    changes: &mut Vec<Change>,
+) {
+    match (minus, plus) {
+        (minus, plus) if minus == plus => {}
+        (Value::Object(minus), Value::Object(plus)) => {
+            for (key, minus_value) in minus {
+                path.push(PathElement::Key(key));
+                match plus.get(key) {
+                    Some(plus_value) => diff_values(path, minus_value, plus_value, changes),
+                    None => changes.push(Change::Removed(format_path(path))),
+                }
+                path.pop();
+            }
+            for key in plus.keys().filter(|key| !minus.contains_key(*key)) {
+                path.push(PathElement::Key(key));
+                changes.push(Change::Added(format_path(path)));
+                path.pop();
+            }
+        }
+        // The file has been added or removed.
+        (Value::Null, Value::Object(_)) if path.is_empty() => {
+            diff_values(path, &EMPTY_OBJECT, plus, changes)
+        }
+        (Value::Object(_), Value::Null) if path.is_empty() => {
+            diff_values(path, minus, &EMPTY_OBJECT, changes)
+        }
+        (Value::Array(minus), Value::Array(plus)) if minus.len() == plus.len() => {
+            for (i, (minus_value, plus_value)) in minus.iter().zip(plus).enumerate() {
+                path.push(PathElement::Index(i));
+                diff_values(path, minus_value, plus_value, changes);
+                path.pop();
+            }
+        }
+        (minus, plus) => changes.push(Change::Modified(
+            format_path(path),
+            format_value(minus),
+            format_value(plus),
+        )),
+    }
+}
+
+lazy_static! {
+    static ref EMPTY_OBJECT: Value = Value::Object(serde_json::Map::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(format: DataFormat, minus: &str, plus: &str) -> Option<Vec<String>> {
+        diff_texts(format, minus, plus)
+            .map(|changes| changes.iter().map(|change| change.to_string()).collect())
+    }
+
+    #[test]
+    fn test_json_changes() {
+        assert_eq!(
+            changes(
+                DataFormat::Json,
+                r#"{"spec": {"replicas": 2, "image": "app:1"}, "old": true}"#,
+                r#"{"spec": {"replicas": 3, "image": "app:1"}, "new.key": [1]}"#,
+            ),
+            Some(vec![
+                "- old".to_string(),
+                "spec.replicas: 2 → 3".to_string(),
+                "+ \"new.key\"".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_toml_changes() {
+        assert_eq!(
+            changes(
+                DataFormat::Toml,
+                "[deps]\nregex = \"1.7\"\n",
+                "[deps]\nregex = \"1.8\"\nserde = \"1.0\"\n",
+            ),
+            Some(vec![
+                "deps.regex: \"1.7\" → \"1.8\"".to_string(),
+                "+ deps.serde".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_added_file_changes() {
+        assert_eq!(
+            changes(DataFormat::Toml, "", "a = 1\nb = 2\n"),
+            Some(vec!["+ a".to_string(), "+ b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_removed_file_changes() {
+        let mut file_content = FileContent::new(DataFormat::Toml);
+        file_content.start_hunk(&[(1, 2), (0, 0)]);
+        file_content.push_line("-a = 1", &State::HunkMinus(DiffType::Unified, None));
+        file_content.push_line("-b = 2", &State::HunkMinus(DiffType::Unified, None));
+        assert_eq!(
+            file_content.changes(&MinusPlus::new(Some("a = 1\nb = 2\n".to_string()), None)),
+            Some(vec![
+                Change::Removed("a".to_string()),
+                Change::Removed("b".to_string()),
+            ])
+        );
+        // The new version is not known to be empty.
+        let mut file_content = FileContent::new(DataFormat::Toml);
+        file_content.start_hunk(&[(1, 1), (1, 0)]);
+        file_content.push_line("-a = 1", &State::HunkMinus(DiffType::Unified, None));
+        assert_eq!(
+            file_content.changes(&MinusPlus::new(Some("a = 1\nb = 2\n".to_string()), None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unparseable_content_has_no_changes() {
+        assert_eq!(changes(DataFormat::Json, "{", "{}"), None);
+    }
+
+    #[test]
+    fn test_start_hunk_requires_contiguous_hunks() {
+        let mut file_content = FileContent::new(DataFormat::Json);
+        file_content.start_hunk(&[(1, 5), (1, 6)]);
+        file_content.start_hunk(&[(6, 2), (7, 2)]);
+        assert!(file_content.is_complete);
+        file_content.start_hunk(&[(10, 2), (11, 2)]);
+        assert!(!file_content.is_complete);
+
+        let mut file_content = FileContent::new(DataFormat::Json);
+        file_content.start_hunk(&[(0, 0), (1, 3)]);
+        assert!(file_content.is_complete);
+    }
+
+    #[test]
+    fn test_hunks_must_reach_end_of_file() {
+        let mut file_content = FileContent::new(DataFormat::Toml);
+        file_content.start_hunk(&[(1, 2), (1, 2)]);
+        file_content.push_line("-a = 1", &State::HunkMinus(DiffType::Unified, None));
+        file_content.push_line("+a = 2", &State::HunkPlus(DiffType::Unified, None));
+        file_content.push_line(" b = 1", &State::HunkZero(DiffType::Unified, None));
+        let plus = |text: &str| MinusPlus::new(None, Some(text.to_string()));
+        assert_eq!(
+            file_content.changes(&plus("a = 2\nb = 1\n")),
+            Some(vec![Change::Modified(
+                "a".to_string(),
+                "1".to_string(),
+                "2".to_string()
+            )])
+        );
+        assert_eq!(file_content.changes(&plus("a = 2\nb = 1\nc = 1\n")), None);
+        assert_eq!(file_content.changes(&MinusPlus::default()), None);
+        // The full versions of the file are preferred to the hunks.
+        assert_eq!(
+            file_content.changes(&MinusPlus::new(
+                Some("a = 1\nc = 1\n".to_string()),
+                Some("a = 2\nb = 1\nc = 1\n".to_string())
+            )),
+            Some(vec![
+                Change::Modified("a".to_string(), "1".to_string(), "2".to_string()),
+                Change::Added("b".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Write;
+
+use crate::ansi::measure_text_width;
+use crate::delta::{State, StateMachine};
+use crate::features::collapsed_file::format_count;
+use crate::handlers::hunk::is_word_diff;
+
+/// The table of contents of the files of a commit, or of the whole input if it has no commits.
+/// The output for the files is held back until the table has been written.
+#[derive(Debug, Default)]
+pub struct Toc {
+    entries: Vec<TocEntry>,
+}
+
+#[derive(Debug)]
+struct TocEntry {
+    description: String,
+    n_minus: usize,
+    n_plus: usize,
+}
+
+impl Toc {
+    /// Count a hunk line of the last file.
+    pub fn count_line(&mut self, state: &State) {
+        if let Some(entry) = self.entries.last_mut() {
+            match state {
+                State::HunkMinus(_, _) => entry.n_minus += 1,
+                State::HunkPlus(_, _) => entry.n_plus += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Return the lines of the table: the file descriptions, aligned, followed by their numbers
+    /// of added and removed lines.
+    fn lines(&self) -> Vec<String> {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| measure_text_width(&entry.description))
+            .max()
+            .unwrap_or(0);
+        self.entries
+            .iter()
+            .map(|entry| {
+                let padding = width - measure_text_width(&entry.description);
+                format!(
+                    "  {}{}  +{} -{}",
+                    entry.description,
+                    " ".repeat(padding),
+                    format_count(entry.n_plus),
+                    format_count(entry.n_minus)
+                )
+            })
+            .collect()
+    }
+}
+
+impl StateMachine<'_> {
+    /// Add the file with the given description to the table of contents, holding back the output
+    /// if it is the first file of the table.
+    pub fn add_toc_entry(&mut self, description: &str) {
+        if !self.config.toc || self.config.color_only || is_word_diff() {
+            return;
+        }
+        if self.toc.is_none() {
+            self.painter.writer.hold();
+        }
+        let toc = self.toc.get_or_insert_with(Toc::default);
+        toc.entries.push(TocEntry {
+            description: description.to_string(),
+            n_minus: 0,
+            n_plus: 0,
+        });
+    }
+
+    /// Write the table of contents, if any, followed by the held output for its files.
+    pub fn write_toc(&mut self) -> std::io::Result<()> {
+        let Some(toc) = self.toc.take() else {
+            return Ok(());
+        };
+        let held_output = self.painter.writer.release();
+        for line in toc.lines() {
+            writeln!(self.painter.writer, "{}", self.config.toc_style.paint(line))?;
+        }
+        self.painter.writer.write_all(&held_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toc_lines() {
+        let entry = |description: &str, n_minus, n_plus| TocEntry {
+            description: description.to_string(),
+            n_minus,
+            n_plus,
+        };
+        let toc = Toc {
+            entries: vec![
+                entry("src/main.rs", 3, 1204),
+                entry("added: README.md", 0, 12),
+            ],
+        };
+        assert_eq!(
+            toc.lines(),
+            vec![
+                "  src/main.rs       +1,204 -3",
+                "  added: README.md  +12 -0",
+            ]
+        );
+    }
+}
@@ -10,6 +10,12 @@ use crate::features::side_by_side::ansifill::ODD_PAD_CHAR;
 pub enum Placeholder<'a> {
     NumberMinus,
     NumberPlus,
+    HunkNumber,
+    HunkOffsetMinus,
+    HunkOffsetPlus,
+    RelativeMinus,
+    RelativePlus,
+    DiffPrefix,
     Str(&'a str),
 }
 
@@ -19,6 +25,12 @@ impl<'a> TryFrom<Option<&'a str>> for Placeholder<'a> {
         match from {
             Some("nm") => Ok(Placeholder::NumberMinus),
             Some("np") => Ok(Placeholder::NumberPlus),
+            Some("hn") => Ok(Placeholder::HunkNumber),
+            Some("hm") => Ok(Placeholder::HunkOffsetMinus),
+            Some("hp") => Ok(Placeholder::HunkOffsetPlus),
+            Some("rm") => Ok(Placeholder::RelativeMinus),
+            Some("rp") => Ok(Placeholder::RelativePlus),
+            Some("prefix") => Ok(Placeholder::DiffPrefix),
             Some(placeholder) => Ok(Placeholder::Str(placeholder)),
             _ => Err(()),
         }
@@ -101,9 +113,13 @@ impl FormatStringPlaceholderData<'_> {
         (
             self.prefix_len
                 + std::cmp::max(
-                    self.placeholder
-                        .as_ref()
-                        .map_or(0, |_| hunk_max_line_number_width),
+                    self.placeholder.as_ref().map_or(0, |placeholder| {
+                        if placeholder == &Placeholder::DiffPrefix {
+                            1
+                        } else {
+                            hunk_max_line_number_width
+                        }
+                    }),
                     self.width.unwrap_or(0),
                 ),
             self.suffix_len,
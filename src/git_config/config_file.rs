@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Opt;
+use crate::env::DeltaEnv;
+use crate::fatal;
+use crate::git_config::GitConfigGet;
+
+/// Settings from delta's own config file, `$XDG_CONFIG_HOME/delta/config.toml` or the file named
+/// by `DELTA_CONFIG`. Top-level keys are options, as in the [delta] git config section, and tables
+/// are features, as in [delta "feature"] sections:
+///
+/// ```toml
+/// features = "my-feature"
+/// line-numbers = true
+///
+/// [my-feature]
+/// syntax-theme = "Dracula"
+/// ```
+///
+/// Theme files in `$XDG_CONFIG_HOME/delta/themes` are read in the same way. A `.toml` theme file
+/// holds the options of a single theme, named after the file, and a `.gitconfig` theme file holds
+/// [delta "theme"] sections, as in themes.gitconfig. Besides `dark` or `light`, a theme may give
+/// its `author`.
+///
+/// Values are stored under their git config keys, e.g. `delta.my-feature.syntax-theme`.
+#[derive(Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+    values: HashMap<String, toml::Value>,
+}
+
+/// A theme file which cannot be read. It is reported only if its theme, the one named after the
+/// file, is selected, or if the themes or the config are shown.
+pub struct SkippedThemeFile {
+    pub name: String,
+    pub error: String,
+}
+
+impl SkippedThemeFile {
+    pub fn warn_if_relevant(&self, opt: &Opt) {
+        let selected = opt
+            .features
+            .as_deref()
+            .unwrap_or("")
+            .split_whitespace()
+            .any(|feature| feature == self.name);
+        if selected || opt.show_themes || opt.show_config {
+            eprintln!("Skipping theme file. {}", self.error);
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Read the config file, if there is one. It is an error for the file named by `DELTA_CONFIG`
+    /// not to exist.
+    pub fn try_create(env: &DeltaEnv) -> Option<Self> {
+        let (path, required) = match (&env.delta_config, &env.config_home) {
+            (Some(path), _) => (path.clone(), true),
+            (None, Some(config_home)) => (config_home.join("delta").join("config.toml"), false),
+            (None, None) => return None,
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => Some(Self::from_contents(&path, &contents)),
+            Err(err) if !required && err.kind() == ErrorKind::NotFound => None,
+            Err(err) => fatal(format!("Failed to read {}: {err}", path.display())),
+        }
+    }
+
+    /// Read the theme files, in order of their names. Also return the theme files which cannot
+    /// be read, and are skipped.
+    pub fn read_themes(env: &DeltaEnv) -> (Vec<Self>, Vec<SkippedThemeFile>) {
+        let (mut theme_files, mut skipped_theme_files) = (Vec::new(), Vec::new());
+        let Some(config_home) = &env.config_home else {
+            return (theme_files, skipped_theme_files);
+        };
+        let Ok(entries) = fs::read_dir(config_home.join("delta").join("themes")) else {
+            return (theme_files, skipped_theme_files);
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .collect();
+        paths.sort();
+        for path in &paths {
+            let (Some(extension), Some(name)) = (
+                path.extension().and_then(|s| s.to_str()),
+                path.file_stem().and_then(|s| s.to_str()),
+            ) else {
+                continue;
+            };
+            let theme_file = match extension {
+                "toml" => fs::read_to_string(path)
+                    .map_err(|err| format!("Failed to read {}: {err}", path.display()))
+                    .and_then(|contents| Self::from_theme_contents(path, name, &contents)),
+                "gitconfig" => Self::from_gitconfig_theme(path),
+                _ => continue,
+            };
+            match theme_file {
+                Ok(theme_file) => theme_files.push(theme_file),
+                Err(error) => skipped_theme_files.push(SkippedThemeFile {
+                    name: name.to_string(),
+                    error,
+                }),
+            }
+        }
+        (theme_files, skipped_theme_files)
+    }
+
+    pub fn from_contents(path: &Path, contents: &str) -> Self {
+        let mut config_file = Self::new(path);
+        for (name, value) in parse_table(path, contents).unwrap_or_else(|err| fatal(err)) {
+            match value {
+                toml::Value::Table(feature) => {
+                    config_file
+                        .insert_feature(&name, feature)
+                        .unwrap_or_else(|err| fatal(err));
+                }
+                value => {
+                    config_file.values.insert(format!("delta.{name}"), value);
+                }
+            }
+        }
+        config_file
+    }
+
+    /// Read a .toml theme file, whose settings are those of the theme `name`.
+    fn from_theme_contents(path: &Path, name: &str, contents: &str) -> Result<Self, String> {
+        let mut config_file = Self::new(path);
+        config_file.insert_feature(name, parse_table(path, contents)?)?;
+        Ok(config_file)
+    }
+
+    /// Read the [delta "theme"] sections of a .gitconfig theme file. Keys outside such sections
+    /// are ignored.
+    fn from_gitconfig_theme(path: &Path) -> Result<Self, String> {
+        let error =
+            |err: git2::Error| format!("Failed to read {}: {}", path.display(), err.message());
+        let config = git2::Config::open(path).map_err(error)?;
+        let mut config_file = Self::new(path);
+        let mut entries = config.entries(Some(r"^delta\..+\.[^.]+$")).map_err(error)?;
+        while let Some(entry) = entries.next() {
+            let entry = entry.map_err(error)?;
+            if let Some(name) = entry.name() {
+                // As in git config, a key without a value is true.
+                let value = match entry.value() {
+                    Some(value) => toml::Value::String(value.to_string()),
+                    None => toml::Value::Boolean(true),
+                };
+                config_file.values.insert(name.to_string(), value);
+            }
+        }
+        Ok(config_file)
+    }
+
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            values: HashMap::new(),
+        }
+    }
+
+    fn insert_feature(&mut self, name: &str, feature: toml::Table) -> Result<(), String> {
+        for (option_name, value) in feature {
+            if value.is_table() {
+                return Err(format!(
+                    "Invalid value for {name}.{option_name} in {}: features cannot be nested",
+                    self.path.display()
+                ));
+            }
+            self.values
+                .insert(format!("delta.{name}.{option_name}"), value);
+        }
+        Ok(())
+    }
+
+    pub fn get<T>(&self, key: &str) -> Option<T>
+    where
+        T: GitConfigGet,
+    {
+        let value = self.values.get(key)?;
+        Some(T::config_file_get(value).unwrap_or_else(|| {
+            fatal(format!(
+                "Invalid value for {} in {}: {value}",
+                key.trim_start_matches("delta."),
+                self.path.display()
+            ))
+        }))
+    }
+
+    /// Call `f` with the key and value, as a string, of each setting whose key matches `regex`.
+    pub fn for_each<F>(&self, regex: &regex::Regex, mut f: F)
+    where
+        F: FnMut(&str, Option<&str>),
+    {
+        let mut keys: Vec<&String> = self.values.keys().filter(|k| regex.is_match(k)).collect();
+        keys.sort();
+        for key in keys {
+            f(key, Some(&value_to_string(&self.values[key])));
+        }
+    }
+}
+
+fn parse_table(path: &Path, contents: &str) -> Result<toml::Table, String> {
+    toml::from_str(contents).map_err(|err| format!("Failed to read {}: {err}", path.display()))
+}
+
+/// Return the value as it would be written in git config.
+pub fn value_to_string(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_values() {
+        let config_file = ConfigFile::from_contents(
+            Path::new("config.toml"),
+            r#"
+features = "my-feature"
+line-numbers = true
+width = 100
+max-line-distance = 0.4
+
+[my-feature]
+syntax-theme = "Dracula"
+tabs = 2
+"#,
+        );
+        assert_eq!(
+            config_file.get::<String>("delta.features"),
+            Some("my-feature".to_string())
+        );
+        assert_eq!(config_file.get::<bool>("delta.line-numbers"), Some(true));
+        assert_eq!(
+            config_file.get::<Option<String>>("delta.width"),
+            Some(Some("100".to_string()))
+        );
+        assert_eq!(config_file.get::<f64>("delta.max-line-distance"), Some(0.4));
+        assert_eq!(
+            config_file.get::<String>("delta.my-feature.syntax-theme"),
+            Some("Dracula".to_string())
+        );
+        assert_eq!(config_file.get::<usize>("delta.my-feature.tabs"), Some(2));
+        assert_eq!(config_file.get::<usize>("delta.tabs"), None);
+
+        let mut entries = Vec::new();
+        let regex = regex::Regex::new(r"^delta\.my-feature\.").unwrap();
+        config_file.for_each(&regex, |name, value| {
+            entries.push((name.to_string(), value.unwrap().to_string()))
+        });
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "delta.my-feature.syntax-theme".to_string(),
+                    "Dracula".to_string()
+                ),
+                ("delta.my-feature.tabs".to_string(), "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_themes() {
+        let config_home = std::env::temp_dir().join("delta__test_read_themes");
+        let themes_dir = config_home.join("delta").join("themes");
+        fs::create_dir_all(&themes_dir).unwrap();
+        fs::write(
+            themes_dir.join("a-theme.gitconfig"),
+            r#"
+[delta]
+    line-numbers = true
+[delta "a-theme"]
+    dark = true
+    syntax-theme = Dracula
+"#,
+        )
+        .unwrap();
+        fs::write(themes_dir.join("b-theme.toml"), "light = true\n").unwrap();
+        fs::write(themes_dir.join("bad-theme.toml"), "light = \n").unwrap();
+        fs::write(
+            themes_dir.join("nested-theme.toml"),
+            "[nested]\nlight = true\n",
+        )
+        .unwrap();
+        let env = DeltaEnv {
+            config_home: Some(config_home.clone()),
+            ..DeltaEnv::default()
+        };
+
+        let (theme_files, skipped_theme_files) = ConfigFile::read_themes(&env);
+        assert_eq!(theme_files.len(), 2);
+        assert_eq!(
+            skipped_theme_files
+                .iter()
+                .map(|skipped| skipped.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["bad-theme", "nested-theme"]
+        );
+        assert_eq!(theme_files[0].get::<bool>("delta.line-numbers"), None);
+        assert_eq!(theme_files[0].get::<bool>("delta.a-theme.dark"), Some(true));
+        assert_eq!(
+            theme_files[0].get::<String>("delta.a-theme.syntax-theme"),
+            Some("Dracula".to_string())
+        );
+        assert_eq!(
+            theme_files[1].get::<bool>("delta.b-theme.light"),
+            Some(true)
+        );
+
+        fs::remove_dir_all(&config_home).unwrap();
+    }
+}
@@ -1,11 +1,14 @@
+mod config_file;
 mod remote;
 
+pub use config_file::{ConfigFile, SkippedThemeFile};
 pub use remote::GitRemoteRepo;
 
 use crate::env::DeltaEnv;
 use regex::Regex;
 use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::convert::TryInto;
 use std::path::Path;
 
 use lazy_static::lazy_static;
@@ -14,13 +17,23 @@ pub struct GitConfig {
     config: git2::Config,
     config_from_env_var: HashMap<String, String>,
     pub enabled: bool,
+    config_files: Vec<ConfigFile>,
     repo: Option<git2::Repository>,
     remote_url: OnceCell<Option<GitRemoteRepo>>,
+    head_commit: OnceCell<Option<String>>,
     // To make GitConfig cloneable when testing (in turn to make Config cloneable):
     #[cfg(test)]
     path: std::path::PathBuf,
 }
 
+/// The commit which last changed a line, according to git blame.
+#[derive(Clone, Debug)]
+pub struct BlameCommit {
+    pub id: String,
+    pub author: String,
+    pub time: git2::Time,
+}
+
 #[cfg(test)]
 impl Clone for GitConfig {
     fn clone(&self) -> Self {
@@ -30,8 +43,10 @@ impl Clone for GitConfig {
             config: git2::Config::open(&self.path).unwrap(),
             config_from_env_var: self.config_from_env_var.clone(),
             enabled: self.enabled,
+            config_files: self.config_files.clone(),
             repo: None,
             remote_url: OnceCell::new(),
+            head_commit: OnceCell::new(),
             path: self.path.clone(),
         }
     }
@@ -60,7 +75,9 @@ impl GitConfig {
                     config_from_env_var: parse_config_from_env_var(env),
                     repo,
                     enabled: true,
+                    config_files: Vec::new(),
                     remote_url: OnceCell::new(),
+                    head_commit: OnceCell::new(),
                 })
             }
             None => None,
@@ -79,8 +96,10 @@ impl GitConfig {
             config: git2::Config::new().unwrap(),
             config_from_env_var: HashMap::new(),
             enabled: true,
+            config_files: Vec::new(),
             repo: None,
             remote_url: OnceCell::new(),
+            head_commit: OnceCell::new(),
             path: std::path::PathBuf::from("/invalid_null.git"),
         })
     }
@@ -103,7 +122,9 @@ impl GitConfig {
                     },
                     repo: None,
                     enabled: true,
+                    config_files: Vec::new(),
                     remote_url: OnceCell::new(),
+                    head_commit: OnceCell::new(),
                     #[cfg(test)]
                     path: path.into(),
                 }
@@ -114,7 +135,45 @@ impl GitConfig {
         }
     }
 
+    /// Add the settings of delta's config file and theme files, if there are any, beneath those
+    /// of `git_config`. If there is no git config, they are used on their own. Also return the
+    /// theme files which cannot be read.
+    pub fn with_config_files(
+        git_config: Option<Self>,
+        env: &DeltaEnv,
+    ) -> (Option<Self>, Vec<SkippedThemeFile>) {
+        let mut config_files: Vec<ConfigFile> = ConfigFile::try_create(env).into_iter().collect();
+        let (theme_files, skipped_theme_files) = ConfigFile::read_themes(env);
+        config_files.extend(theme_files);
+        if config_files.is_empty() {
+            return (git_config, skipped_theme_files);
+        }
+        let mut git_config = git_config.unwrap_or_else(|| Self {
+            config: git2::Config::new().unwrap(),
+            config_from_env_var: HashMap::new(),
+            enabled: true,
+            config_files: Vec::new(),
+            repo: None,
+            remote_url: OnceCell::new(),
+            head_commit: OnceCell::new(),
+            #[cfg(test)]
+            path: std::path::PathBuf::from("/invalid_null.git"),
+        });
+        git_config.config_files = config_files;
+        (Some(git_config), skipped_theme_files)
+    }
+
+    /// Return the value of `key` in git config or, failing that, in delta's config files.
     pub fn get<T>(&self, key: &str) -> Option<T>
+    where
+        T: GitConfigGet,
+    {
+        self.get_from_git(key)
+            .or_else(|| self.get_from_config_files(key))
+    }
+
+    /// Return the value of `key` in git config only.
+    pub fn get_from_git<T>(&self, key: &str) -> Option<T>
     where
         T: GitConfigGet,
     {
@@ -125,6 +184,102 @@ impl GitConfig {
         }
     }
 
+    /// Return the value of `key` in delta's config file and theme files only.
+    pub fn get_from_config_files<T>(&self, key: &str) -> Option<T>
+    where
+        T: GitConfigGet,
+    {
+        self.config_files.iter().find_map(|file| file.get(key))
+    }
+
+    /// Return the content of the blob whose id starts with `id_prefix`, if it is in the repository.
+    pub fn get_blob_content(&self, id_prefix: &str) -> Option<Vec<u8>> {
+        let object = self
+            .repo
+            .as_ref()?
+            .find_object_by_prefix(id_prefix, Some(git2::ObjectType::Blob))
+            .ok()?;
+        Some(object.as_blob()?.content().to_vec())
+    }
+
+    /// Return, for each line of the file `path` (relative to the repository root), the commit
+    /// which last changed it, as of the commit `newest_commit`, or HEAD if None. If `content` is
+    /// given, the lines are those of `content` instead, and lines which are not present in the
+    /// file as of that commit have no commit.
+    pub fn get_blame(
+        &self,
+        path: &str,
+        newest_commit: Option<&str>,
+        content: Option<&[u8]>,
+    ) -> Option<Vec<Option<BlameCommit>>> {
+        let repo = self.repo.as_ref()?;
+        let newest_commit = match newest_commit {
+            Some(rev) => repo.revparse_single(rev).ok()?.peel_to_commit().ok()?,
+            None => repo.head().ok()?.peel_to_commit().ok()?,
+        };
+        let mut options = git2::BlameOptions::new();
+        options.newest_commit(newest_commit.id());
+        let file_blame = repo.blame_file(Path::new(path), Some(&mut options)).ok()?;
+        let buffer_blame;
+        let blame = match content {
+            Some(content) => {
+                buffer_blame = file_blame.blame_buffer(content).ok()?;
+                &buffer_blame
+            }
+            None => &file_blame,
+        };
+        let mut commits = Vec::new();
+        for hunk in blame.iter() {
+            // Lines of the buffer which are not in the file as of `newest_commit` have a zero id,
+            // and no signature.
+            let commit = (!hunk.final_commit_id().is_zero()).then(|| {
+                let signature = hunk.final_signature();
+                BlameCommit {
+                    id: hunk.final_commit_id().to_string(),
+                    author: signature.name().unwrap_or_default().to_string(),
+                    time: signature.when(),
+                }
+            });
+            commits.extend(std::iter::repeat_n(commit, hunk.lines_in_hunk()));
+        }
+        Some(commits)
+    }
+
+    /// Return the id of the commit at HEAD.
+    pub fn get_head_commit(&self) -> Option<&str> {
+        self.head_commit
+            .get_or_init(|| {
+                let commit = self.repo.as_ref()?.head().ok()?.peel_to_commit().ok()?;
+                Some(commit.id().to_string())
+            })
+            .as_deref()
+    }
+
+    /// Return `absolute_path` relative to the root of the repository's working tree.
+    pub fn get_path_relative_to_repo_root(&self, absolute_path: &Path) -> Option<String> {
+        let workdir = self.repo.as_ref()?.workdir()?;
+        let relative_path = absolute_path.strip_prefix(workdir).ok()?;
+        let components: Vec<_> = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+        Some(components.join("/"))
+    }
+
+    /// Return the value of the gitattribute `name` for `path`, relative to the repository root.
+    pub fn get_attribute(&self, path: &str, name: &str) -> Option<git2::AttrValue<'_>> {
+        let value = self
+            .repo
+            .as_ref()?
+            .get_attr(
+                std::path::Path::new(path),
+                name,
+                git2::AttrCheckFlags::FILE_THEN_INDEX,
+            )
+            .ok()?;
+        Some(git2::AttrValue::from_string(value))
+    }
+
     #[cfg(test)]
     fn get_remote_url_impl(&self) -> Option<GitRemoteRepo> {
         GitRemoteRepo::for_testing()
@@ -132,13 +287,30 @@ impl GitConfig {
 
     #[cfg(not(test))]
     fn get_remote_url_impl(&self) -> Option<GitRemoteRepo> {
-        use std::str::FromStr;
         self.repo
             .as_ref()?
             .find_remote("origin")
             .ok()?
             .url()
-            .and_then(|url| GitRemoteRepo::from_str(url).ok())
+            .and_then(|url| GitRemoteRepo::from_url(url, &self.get_forges()).ok())
+    }
+
+    /// Return the kinds of self-hosted forges, by hostname, as configured by e.g.
+    /// `delta.forge.git.example.com = gitlab`.
+    #[cfg(not(test))]
+    fn get_forges(&self) -> HashMap<String, remote::Forge> {
+        use crate::fatal;
+        let mut forges = HashMap::new();
+        self.for_each(r"^delta\.forge\.", |name, value| {
+            let host = name.trim_start_matches("delta.forge.").to_lowercase();
+            match value.unwrap_or_default().parse() {
+                Ok(forge) => {
+                    forges.insert(host, forge);
+                }
+                Err(err) => fatal(format!("Invalid value for {name}: {err}")),
+            }
+        });
+        forges
     }
 
     pub fn get_remote_url(&self) -> &Option<GitRemoteRepo> {
@@ -155,6 +327,12 @@ impl GitConfig {
             let name = entry.name().unwrap();
             f(name, entry.value());
         }
+        if !self.config_files.is_empty() {
+            let regex = Regex::new(regex).unwrap();
+            for config_file in &self.config_files {
+                config_file.for_each(&regex, &mut f);
+            }
+        }
     }
 }
 
@@ -206,6 +384,11 @@ pub trait GitConfigGet {
     fn git_config_get(key: &str, git_config: &GitConfig) -> Option<Self>
     where
         Self: Sized;
+
+    /// Convert a value from delta's config file, or return None if it has the wrong type.
+    fn config_file_get(value: &toml::Value) -> Option<Self>
+    where
+        Self: Sized;
 }
 
 impl GitConfigGet for String {
@@ -215,6 +398,15 @@ impl GitConfigGet for String {
             None => git_config.config.get_string(key).ok(),
         }
     }
+
+    fn config_file_get(value: &toml::Value) -> Option<Self> {
+        match value {
+            toml::Value::String(_) | toml::Value::Integer(_) | toml::Value::Float(_) => {
+                Some(config_file::value_to_string(value))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl GitConfigGet for Option<String> {
@@ -227,6 +419,10 @@ impl GitConfigGet for Option<String> {
             },
         }
     }
+
+    fn config_file_get(value: &toml::Value) -> Option<Self> {
+        String::config_file_get(value).map(Some)
+    }
 }
 
 impl GitConfigGet for bool {
@@ -237,6 +433,14 @@ impl GitConfigGet for bool {
             _ => git_config.config.get_bool(key).ok(),
         }
     }
+
+    fn config_file_get(value: &toml::Value) -> Option<Self> {
+        match value {
+            toml::Value::Boolean(value) => Some(*value),
+            toml::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 impl GitConfigGet for usize {
@@ -251,6 +455,14 @@ impl GitConfigGet for usize {
             _ => None,
         }
     }
+
+    fn config_file_get(value: &toml::Value) -> Option<Self> {
+        match value {
+            toml::Value::Integer(value) => (*value).try_into().ok(),
+            toml::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 impl GitConfigGet for f64 {
@@ -265,6 +477,15 @@ impl GitConfigGet for f64 {
             _ => None,
         }
     }
+
+    fn config_file_get(value: &toml::Value) -> Option<Self> {
+        match value {
+            toml::Value::Float(value) => Some(*value),
+            toml::Value::Integer(value) => Some(*value as f64),
+            toml::Value::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
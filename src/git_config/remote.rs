@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::result::Result;
 use std::str::FromStr;
 
@@ -8,27 +9,182 @@ use crate::errors::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GitRemoteRepo {
-    GitHub { slug: String },
-    GitLab { slug: String },
-    SourceHut { slug: String },
-    Codeberg { slug: String },
+    GitHub {
+        slug: String,
+    },
+    GitLab {
+        slug: String,
+    },
+    SourceHut {
+        slug: String,
+    },
+    Codeberg {
+        slug: String,
+    },
+    Bitbucket {
+        slug: String,
+    },
+    // The slug is "organization/project/repo".
+    AzureDevOps {
+        slug: String,
+    },
+    // A repository on a self-hosted forge, see delta.forge.<host>.
+    SelfHosted {
+        forge: Forge,
+        host: String,
+        slug: String,
+    },
+}
+
+/// The kinds of forge, which differ in the URL schemes of their web interfaces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    SourceHut,
+    Gitea,
+    Bitbucket,
+    AzureDevOps,
+}
+
+impl FromStr for Forge {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            "sourcehut" => Ok(Self::SourceHut),
+            "gitea" | "forgejo" => Ok(Self::Gitea),
+            "bitbucket" => Ok(Self::Bitbucket),
+            "azure-devops" => Ok(Self::AzureDevOps),
+            _ => Err(anyhow!(
+                "Unknown forge '{s}'. Valid forges are github, gitlab, sourcehut, gitea, \
+                 forgejo, bitbucket and azure-devops."
+            )),
+        }
+    }
 }
 
 impl GitRemoteRepo {
-    pub fn format_commit_url(&self, commit: &str) -> String {
+    /// Parse a remote URL, using `forges`, a map from hostnames of self-hosted forges to their
+    /// kind, in addition to the hosted forges.
+    pub fn from_url(url: &str, forges: &HashMap<String, Forge>) -> Result<Self, Error> {
+        if let Some((host, path)) = split_remote_url(url) {
+            let hostname = host.split(':').next().unwrap_or_default().to_lowercase();
+            if let Some(&forge) = forges.get(&hostname) {
+                let slug = match forge {
+                    // Azure DevOps Server URLs are similar to those of Azure DevOps Services.
+                    Forge::AzureDevOps => path
+                        .trim_start_matches("v3/")
+                        .split('/')
+                        .filter(|component| *component != "_git")
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    _ => path.to_string(),
+                };
+                return Ok(Self::SelfHosted { forge, host, slug });
+            }
+        }
+        Self::from_str(url)
+    }
+
+    /// Return the kind of forge hosting the repository, its hostname, and the repository slug.
+    fn location(&self) -> (Forge, &str, &str) {
         match self {
-            Self::GitHub { slug } => {
-                format!("https://github.com/{slug}/commit/{commit}")
+            Self::GitHub { slug } => (Forge::GitHub, "github.com", slug),
+            Self::GitLab { slug } => (Forge::GitLab, "gitlab.com", slug),
+            Self::SourceHut { slug } => (Forge::SourceHut, "git.sr.ht", slug),
+            Self::Codeberg { slug } => (Forge::Gitea, "codeberg.org", slug),
+            Self::Bitbucket { slug } => (Forge::Bitbucket, "bitbucket.org", slug),
+            Self::AzureDevOps { slug } => (Forge::AzureDevOps, "dev.azure.com", slug),
+            Self::SelfHosted { forge, host, slug } => (*forge, host, slug),
+        }
+    }
+
+    pub fn format_commit_url(&self, commit: &str) -> String {
+        let (forge, host, slug) = self.location();
+        match forge {
+            Forge::GitHub | Forge::SourceHut | Forge::Gitea => {
+                format!("https://{host}/{slug}/commit/{commit}")
             }
-            Self::GitLab { slug } => {
-                format!("https://gitlab.com/{slug}/-/commit/{commit}")
+            Forge::GitLab => format!("https://{host}/{slug}/-/commit/{commit}"),
+            Forge::Bitbucket => format!("https://{host}/{slug}/commits/{commit}"),
+            Forge::AzureDevOps => {
+                format!(
+                    "{}/commit/{commit}",
+                    format_azure_devops_repo_url(host, slug)
+                )
+            }
+        }
+    }
+
+    /// Return the URL of a file, and optionally a line in it, as of the given commit. `path` is
+    /// relative to the repository root.
+    pub fn format_file_url(&self, commit: &str, path: &str, line_number: Option<usize>) -> String {
+        let (forge, host, slug) = self.location();
+        let path = encode_path(path);
+        let (url, anchor) = match forge {
+            Forge::GitHub => (format!("https://{host}/{slug}/blob/{commit}/{path}"), "#L"),
+            Forge::GitLab => (
+                format!("https://{host}/{slug}/-/blob/{commit}/{path}"),
+                "#L",
+            ),
+            Forge::SourceHut => (
+                format!("https://{host}/{slug}/tree/{commit}/item/{path}"),
+                "#L",
+            ),
+            Forge::Gitea => (
+                format!("https://{host}/{slug}/src/commit/{commit}/{path}"),
+                "#L",
+            ),
+            Forge::Bitbucket => (
+                format!("https://{host}/{slug}/src/{commit}/{path}"),
+                "#lines-",
+            ),
+            Forge::AzureDevOps => {
+                let url = format!(
+                    "{}?path=/{path}&version=GC{commit}",
+                    format_azure_devops_repo_url(host, slug)
+                );
+                // Azure DevOps selects a range of characters rather than lines.
+                return match line_number {
+                    Some(n) => format!(
+                        "{url}&line={n}&lineEnd={}&lineStartColumn=1&lineEndColumn=1",
+                        n + 1
+                    ),
+                    None => url,
+                };
             }
-            Self::SourceHut { slug } => {
-                format!("https://git.sr.ht/{slug}/commit/{commit}")
+        };
+        match line_number {
+            Some(n) => format!("{url}{anchor}{n}"),
+            None => url,
+        }
+    }
+
+    /// Return the URL of the issue, or pull or merge request, with the given number, if the forge
+    /// has an issue tracker at a known URL.
+    pub fn format_issue_url(&self, number: &str) -> Option<String> {
+        let (forge, host, slug) = self.location();
+        match forge {
+            Forge::GitHub | Forge::Gitea | Forge::Bitbucket => {
+                Some(format!("https://{host}/{slug}/issues/{number}"))
             }
-            Self::Codeberg { slug } => {
-                format!("https://codeberg.org/{slug}/commit/{commit}")
+            Forge::GitLab => Some(format!("https://{host}/{slug}/-/issues/{number}")),
+            Forge::AzureDevOps => {
+                let project = slug.rsplit_once('/').map_or(slug, |(project, _)| project);
+                Some(format!("https://{host}/{project}/_workitems/edit/{number}"))
             }
+            // SourceHut issue trackers are independent of repositories.
+            Forge::SourceHut => None,
+        }
+    }
+
+    /// Return the regex matching references to issues in commit messages, capturing the number.
+    pub fn issue_reference_regex(&self) -> &'static Regex {
+        match self.location() {
+            (Forge::GitHub, _, _) => &GITHUB_ISSUE_REFERENCE,
+            _ => &ISSUE_REFERENCE,
         }
     }
 
@@ -40,7 +196,64 @@ impl GitRemoteRepo {
     }
 }
 
+fn format_azure_devops_repo_url(host: &str, slug: &str) -> String {
+    match slug.rsplit_once('/') {
+        Some((project, repo)) => format!("https://{host}/{project}/_git/{repo}"),
+        None => format!("https://{host}/{slug}"),
+    }
+}
+
+/// Split a remote URL, either a URL with a scheme or an scp-like SSH address, into the host of the
+/// web interface of its forge and the path of the repository.
+fn split_remote_url(url: &str) -> Option<(String, String)> {
+    let (host, path) = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+            // The port of an HTTP server is also that of the web interface, unlike an SSH port.
+            if scheme.starts_with("http") {
+                (host, path)
+            } else {
+                (host.split(':').next()?, path)
+            }
+        }
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (
+                authority
+                    .rsplit_once('@')
+                    .map_or(authority, |(_, host)| host),
+                path,
+            )
+        }
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), path.to_string()))
+}
+
+/// Percent-encode the characters of a path which may not appear in a URL path as they are.
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
 lazy_static! {
+    static ref ISSUE_REFERENCE: Regex = Regex::new(r"\B#([0-9]+)\b").unwrap();
+    static ref GITHUB_ISSUE_REFERENCE: Regex = Regex::new(r"(?:\B#|\bGH-)([0-9]+)\b").unwrap();
     static ref GITHUB_REMOTE_URL: Regex = Regex::new(
         r"(?x)
         ^
@@ -83,6 +296,40 @@ lazy_static! {
         "
     )
     .unwrap();
+    static ref BITBUCKET_REMOTE_URL: Regex = Regex::new(
+        r"(?x)
+        ^
+        (?:https://|ssh://)? # Support HTTPS, SSH and scp-like SSH URLs
+        (?:[^@/]+@)?      # HTTPS URLs may include a user, as SSH URLs do
+        bitbucket\.org
+        [:/]              # This separator differs between SSH and HTTPS URLs
+        ([^/]+)           # Capture the workspace name
+        /
+        (.+?)             # Capture the repo name (lazy to avoid consuming '.git' if present)
+        (?:\.git)?        # Non-capturing group to consume '.git' if present
+        $
+        "
+    )
+    .unwrap();
+    static ref AZURE_DEVOPS_REMOTE_URL: Regex = Regex::new(
+        r"(?x)
+        ^
+        (?:
+            https://(?:[^@]+@)?dev\.azure\.com/    # HTTPS URLs, optionally with a user
+            |
+            (?:[^@]+@)?ssh\.dev\.azure\.com:v3/   # SSH URLs
+        )
+        ([^/]+)           # Capture the organization name
+        /
+        ([^/]+)           # Capture the project name
+        /
+        (?:_git/)?        # Only present in HTTPS URLs
+        ([^/]+?)          # Capture the repo name
+        (?:\.git)?
+        $
+        "
+    )
+    .unwrap();
     static ref CODEBERG_REMOTE_URL: Regex = Regex::new(
         r"(?x)
         ^
@@ -135,8 +382,27 @@ impl FromStr for GitRemoteRepo {
                     repo = caps.get(2).unwrap().as_str()
                 ),
             })
+        } else if let Some(caps) = BITBUCKET_REMOTE_URL.captures(s) {
+            Ok(Self::Bitbucket {
+                slug: format!(
+                    "{workspace}/{repo}",
+                    workspace = caps.get(1).unwrap().as_str(),
+                    repo = caps.get(2).unwrap().as_str()
+                ),
+            })
+        } else if let Some(caps) = AZURE_DEVOPS_REMOTE_URL.captures(s) {
+            Ok(Self::AzureDevOps {
+                slug: format!(
+                    "{organization}/{project}/{repo}",
+                    organization = caps.get(1).unwrap().as_str(),
+                    project = caps.get(2).unwrap().as_str(),
+                    repo = caps.get(3).unwrap().as_str()
+                ),
+            })
         } else {
-            Err(anyhow!("Not a GitHub, GitLab, SourceHut or Codeberg repo."))
+            Err(anyhow!(
+                "Not a GitHub, GitLab, SourceHut, Codeberg, Bitbucket or Azure DevOps repo."
+            ))
         }
     }
 }
@@ -288,4 +554,253 @@ mod tests {
             format!("https://codeberg.org/dnkl/foot/commit/{commit_hash}")
         )
     }
+
+    #[test]
+    fn test_format_file_links() {
+        let commit = "1c072856ebf12419378c5098ad543c497197c6da";
+        let cases = [
+            (
+                GitRemoteRepo::GitHub {
+                    slug: "dandavison/delta".to_string(),
+                },
+                "https://github.com/dandavison/delta/blob/1c072856ebf12419378c5098ad543c497197c6da/src/main.rs#L42",
+            ),
+            (
+                GitRemoteRepo::GitLab {
+                    slug: "proj/grp/repo".to_string(),
+                },
+                "https://gitlab.com/proj/grp/repo/-/blob/1c072856ebf12419378c5098ad543c497197c6da/src/main.rs#L42",
+            ),
+            (
+                GitRemoteRepo::SourceHut {
+                    slug: "~someuser/somerepo".to_string(),
+                },
+                "https://git.sr.ht/~someuser/somerepo/tree/1c072856ebf12419378c5098ad543c497197c6da/item/src/main.rs#L42",
+            ),
+            (
+                GitRemoteRepo::Codeberg {
+                    slug: "dnkl/foot".to_string(),
+                },
+                "https://codeberg.org/dnkl/foot/src/commit/1c072856ebf12419378c5098ad543c497197c6da/src/main.rs#L42",
+            ),
+            (
+                GitRemoteRepo::Bitbucket {
+                    slug: "someworkspace/somerepo".to_string(),
+                },
+                "https://bitbucket.org/someworkspace/somerepo/src/1c072856ebf12419378c5098ad543c497197c6da/src/main.rs#lines-42",
+            ),
+            (
+                GitRemoteRepo::AzureDevOps {
+                    slug: "someorg/someproject/somerepo".to_string(),
+                },
+                "https://dev.azure.com/someorg/someproject/_git/somerepo?path=/src/main.rs&version=GC1c072856ebf12419378c5098ad543c497197c6da&line=42&lineEnd=43&lineStartColumn=1&lineEndColumn=1",
+            ),
+        ];
+        for (repo, expected) in cases {
+            assert_eq!(
+                repo.format_file_url(commit, "src/main.rs", Some(42)),
+                expected
+            );
+        }
+        let repo = GitRemoteRepo::GitHub {
+            slug: "dandavison/delta".to_string(),
+        };
+        assert_eq!(
+            repo.format_file_url(commit, "docs/read me#1.md", None),
+            "https://github.com/dandavison/delta/blob/1c072856ebf12419378c5098ad543c497197c6da/docs/read%20me%231.md"
+        );
+    }
+
+    #[test]
+    fn test_parse_bitbucket_urls() {
+        let urls = &[
+            "https://bitbucket.org/someworkspace/somerepo.git",
+            "https://someuser@bitbucket.org/someworkspace/somerepo.git",
+            "git@bitbucket.org:someworkspace/somerepo.git",
+            "git@bitbucket.org:someworkspace/somerepo",
+            "ssh://git@bitbucket.org/someworkspace/somerepo.git",
+        ];
+        for url in urls {
+            assert_eq!(
+                GitRemoteRepo::from_str(url).unwrap(),
+                GitRemoteRepo::Bitbucket {
+                    slug: "someworkspace/somerepo".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_azure_devops_urls() {
+        let urls = &[
+            "https://dev.azure.com/someorg/someproject/_git/somerepo",
+            "https://someorg@dev.azure.com/someorg/someproject/_git/somerepo",
+            "git@ssh.dev.azure.com:v3/someorg/someproject/somerepo",
+        ];
+        for url in urls {
+            assert_eq!(
+                GitRemoteRepo::from_str(url).unwrap(),
+                GitRemoteRepo::AzureDevOps {
+                    slug: "someorg/someproject/somerepo".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_self_hosted_urls() {
+        let forges = HashMap::from([
+            ("git.example.com".to_string(), Forge::GitLab),
+            ("tfs.example.com".to_string(), Forge::AzureDevOps),
+        ]);
+        for (url, expected_host, expected_slug) in &[
+            (
+                "https://git.example.com/grp/subgrp/repo.git",
+                "git.example.com",
+                "grp/subgrp/repo",
+            ),
+            (
+                "https://git.example.com:8443/grp/repo",
+                "git.example.com:8443",
+                "grp/repo",
+            ),
+            (
+                "git@git.example.com:grp/repo.git",
+                "git.example.com",
+                "grp/repo",
+            ),
+            (
+                "ssh://git@git.example.com:2222/grp/repo.git",
+                "git.example.com",
+                "grp/repo",
+            ),
+            (
+                "git@Git.Example.com:grp/repo.git",
+                "Git.Example.com",
+                "grp/repo",
+            ),
+        ] {
+            assert_eq!(
+                GitRemoteRepo::from_url(url, &forges).unwrap(),
+                GitRemoteRepo::SelfHosted {
+                    forge: Forge::GitLab,
+                    host: expected_host.to_string(),
+                    slug: expected_slug.to_string(),
+                }
+            );
+        }
+        assert_eq!(
+            GitRemoteRepo::from_url(
+                "https://tfs.example.com/DefaultCollection/someproject/_git/somerepo",
+                &forges
+            )
+            .unwrap(),
+            GitRemoteRepo::SelfHosted {
+                forge: Forge::AzureDevOps,
+                host: "tfs.example.com".to_string(),
+                slug: "DefaultCollection/someproject/somerepo".to_string(),
+            }
+        );
+        // Hosted forges are recognized regardless of the configured forges.
+        assert_eq!(
+            GitRemoteRepo::from_url("git@github.com:dandavison/delta.git", &forges).unwrap(),
+            GitRemoteRepo::GitHub {
+                slug: "dandavison/delta".to_string()
+            }
+        );
+        assert!(GitRemoteRepo::from_url("git@git.other.com:grp/repo.git", &forges).is_err());
+    }
+
+    #[test]
+    fn test_parse_forge() {
+        assert_eq!("gitlab".parse::<Forge>().unwrap(), Forge::GitLab);
+        assert_eq!("Forgejo".parse::<Forge>().unwrap(), Forge::Gitea);
+        assert_eq!("azure-devops".parse::<Forge>().unwrap(), Forge::AzureDevOps);
+        assert!("gitweb".parse::<Forge>().is_err());
+    }
+
+    #[test]
+    fn test_format_self_hosted_and_other_forge_commit_links() {
+        let commit_hash = "d3b07384d113edec49eaa6238ad5ff00";
+        let cases = [
+            (
+                GitRemoteRepo::SelfHosted {
+                    forge: Forge::GitLab,
+                    host: "git.example.com".to_string(),
+                    slug: "grp/repo".to_string(),
+                },
+                format!("https://git.example.com/grp/repo/-/commit/{commit_hash}"),
+            ),
+            (
+                GitRemoteRepo::SelfHosted {
+                    forge: Forge::Gitea,
+                    host: "gitea.example.com:3000".to_string(),
+                    slug: "someuser/somerepo".to_string(),
+                },
+                format!("https://gitea.example.com:3000/someuser/somerepo/commit/{commit_hash}"),
+            ),
+            (
+                GitRemoteRepo::Bitbucket {
+                    slug: "someworkspace/somerepo".to_string(),
+                },
+                format!("https://bitbucket.org/someworkspace/somerepo/commits/{commit_hash}"),
+            ),
+            (
+                GitRemoteRepo::AzureDevOps {
+                    slug: "someorg/someproject/somerepo".to_string(),
+                },
+                format!(
+                    "https://dev.azure.com/someorg/someproject/_git/somerepo/commit/{commit_hash}"
+                ),
+            ),
+        ];
+        for (repo, expected) in cases {
+            assert_eq!(repo.format_commit_url(commit_hash), expected);
+        }
+    }
+
+    #[test]
+    fn test_issue_references() {
+        let github = GitRemoteRepo::GitHub {
+            slug: "dandavison/delta".to_string(),
+        };
+        let gitlab = GitRemoteRepo::SelfHosted {
+            forge: Forge::GitLab,
+            host: "git.example.com".to_string(),
+            slug: "grp/repo".to_string(),
+        };
+        let references = |repo: &GitRemoteRepo, line: &str| -> Vec<String> {
+            repo.issue_reference_regex()
+                .captures_iter(line)
+                .map(|caps| caps[1].to_string())
+                .collect()
+        };
+        let line = "Fix #12 and GH-34, see page#5";
+        assert_eq!(references(&github, line), ["12", "34"]);
+        assert_eq!(references(&gitlab, line), ["12"]);
+
+        assert_eq!(
+            github.format_issue_url("12").unwrap(),
+            "https://github.com/dandavison/delta/issues/12"
+        );
+        assert_eq!(
+            gitlab.format_issue_url("12").unwrap(),
+            "https://git.example.com/grp/repo/-/issues/12"
+        );
+        assert_eq!(
+            GitRemoteRepo::AzureDevOps {
+                slug: "someorg/someproject/somerepo".to_string(),
+            }
+            .format_issue_url("12")
+            .unwrap(),
+            "https://dev.azure.com/someorg/someproject/_workitems/edit/12"
+        );
+        assert_eq!(
+            GitRemoteRepo::SourceHut {
+                slug: "~someuser/somerepo".to_string(),
+            }
+            .format_issue_url("12"),
+            None
+        );
+    }
 }
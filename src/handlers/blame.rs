@@ -2,6 +2,7 @@ use chrono::{DateTime, FixedOffset};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::borrow::Cow;
+use std::io::Write;
 use unicode_width::UnicodeWidthStr;
 
 use crate::ansi::measure_text_width;
@@ -10,11 +11,12 @@ use crate::config;
 use crate::config::delta_unreachable;
 use crate::delta::{self, State, StateMachine};
 use crate::fatal;
+use crate::features;
 use crate::format::{self, FormatStringSimple, Placeholder};
 use crate::format::{make_placeholder_regex, parse_line_number_format};
 use crate::paint::{self, BgShouldFill, StyleSectionSpecifier};
 use crate::style::Style;
-use crate::utils::process;
+use crate::utils::{self, process};
 
 #[derive(Clone, Debug)]
 pub enum BlameLineNumbers {
@@ -66,12 +68,30 @@ impl StateMachine<'_> {
                     is_repeat,
                 );
 
+                let mut line_number = metadata_style.paint(&line_number).to_string();
+                if self.config.hyperlinks && !line_number.trim().is_empty() {
+                    if let Some(absolute_path) = self
+                        .get_filename()
+                        .and_then(|file| utils::path::absolute_path(&file, self.config))
+                    {
+                        line_number = features::hyperlinks::format_osc8_file_hyperlink(
+                            absolute_path,
+                            Some(blame.line_number),
+                            None,
+                            None,
+                            &line_number,
+                            self.config,
+                        )
+                        .to_string();
+                    }
+                }
+
                 write!(
                     self.painter.writer,
                     "{}{}{}{}",
                     metadata_style.paint(&formatted_blame_metadata),
                     separator_style.paint(nr_prefix),
-                    metadata_style.paint(&line_number),
+                    line_number,
                     separator_style.paint(nr_suffix),
                 )?;
 
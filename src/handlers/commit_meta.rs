@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::io::Write;
 
 use super::draw;
-use crate::delta::{State, StateMachine};
+use crate::delta::{should_hyperlink_raw_lines, State, StateMachine};
 use crate::features;
+use crate::features::navigate::NavigateLevel;
 
 impl StateMachine<'_> {
     #[inline]
@@ -16,16 +18,44 @@ impl StateMachine<'_> {
         }
         let mut handled_line = false;
         self.painter.paint_buffered_minus_and_plus_lines();
+        self.painter.emit()?;
+        self.finish_file()?;
         self.handle_pending_line_with_diff_name()?;
+        self.write_toc()?;
+        self.skip_file = false;
+        self.commit = features::inline_blame::parse_commit_hash(&self.line);
         self.state = State::CommitMeta;
+        self.painter.emit()?;
+        draw::write_semantic_mark(&mut self.painter.writer, NavigateLevel::Commit, self.config)?;
         if self.should_handle() {
-            self.painter.emit()?;
             self._handle_commit_meta_header_line()?;
             handled_line = true
         }
         Ok(handled_line)
     }
 
+    /// Emit a line of the commit metadata following the header line, such as a line of the commit
+    /// message, with hyperlinks. As for other lines emitted unaltered, this is only done when
+    /// output is going to a tty.
+    pub fn handle_commit_meta_body_line(&mut self) -> std::io::Result<bool> {
+        if self.state != State::CommitMeta
+            || !should_hyperlink_raw_lines(self.config)
+            || !self.should_handle()
+        {
+            return Ok(false);
+        }
+        self.painter.emit()?;
+        writeln!(
+            self.painter.writer,
+            "{}",
+            features::hyperlinks::format_commit_message_line_with_osc8_hyperlinks(
+                &self.raw_line,
+                self.config
+            )
+        )?;
+        Ok(true)
+    }
+
     fn _handle_commit_meta_header_line(&mut self) -> std::io::Result<()> {
         if self.config.commit_style.is_omitted {
             return Ok(());
@@ -48,7 +78,7 @@ impl StateMachine<'_> {
         };
 
         draw_fn(
-            self.painter.writer,
+            &mut self.painter.writer,
             &format!("{}{}", formatted_line, if pad { " " } else { "" }),
             &format!("{}{}", formatted_raw_line, if pad { " " } else { "" }),
             "",
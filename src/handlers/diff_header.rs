@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::io::Write;
 use std::path::Path;
 
 use unicode_segmentation::UnicodeSegmentation;
@@ -6,13 +7,15 @@ use unicode_segmentation::UnicodeSegmentation;
 use super::draw;
 use crate::config::Config;
 use crate::delta::{DiffType, Source, State, StateMachine};
+use crate::features::navigate::NavigateLevel;
+use crate::handlers::hunk_header::parse_unified_hunk_lengths;
 use crate::paint::Painter;
 use crate::{features, utils};
 
 // https://git-scm.com/docs/git-config#Documentation/git-config.txt-diffmnemonicPrefix
 const DIFF_PREFIXES: [&str; 6] = ["a/", "b/", "c/", "i/", "o/", "w/"];
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum FileEvent {
     Added,
     Change,
@@ -51,6 +54,22 @@ impl StateMachine<'_> {
         Ok(handled_line)
     }
 
+    /// Record the blob ids of the old and new versions of the file from the "index" line. The line
+    /// itself is not handled here.
+    pub fn handle_diff_header_index_line(&mut self) -> bool {
+        if matches!(self.state, State::DiffHeader(DiffType::Unified)) {
+            if let Some((minus, plus)) = self
+                .line
+                .strip_prefix("index ")
+                .and_then(|line_suf| line_suf.split_once(".."))
+            {
+                self.minus_blob_id = Some(minus.to_string());
+                self.plus_blob_id = plus.split(' ').next().map(str::to_string);
+            }
+        }
+        false
+    }
+
     fn should_write_generic_diff_header_header_line(&mut self) -> std::io::Result<bool> {
         // In color_only mode, raw_line's structure shouldn't be changed.
         // So it needs to avoid fn _handle_diff_header_header_line
@@ -89,6 +108,9 @@ impl StateMachine<'_> {
 
         utils::path::relativize_path_maybe(&mut path_or_mode, self.config);
         self.minus_file = path_or_mode;
+        if matches!(file_event, FileEvent::Rename | FileEvent::Copy) {
+            self.rename_or_copy = Some(file_event.clone());
+        }
         self.minus_file_event = file_event;
 
         if self.source == Source::DiffUnified {
@@ -139,13 +161,84 @@ impl StateMachine<'_> {
             && self.handled_diff_header_header_line_file_pair != self.current_file_pair
         {
             self.painter.emit()?;
-            self._handle_diff_header_header_line(self.source == Source::DiffUnified)?;
-            self.handled_diff_header_header_line_file_pair
-                .clone_from(&self.current_file_pair);
+            self.finish_file()?;
+            self.skip_file = !self.should_display_file();
+            self.skipped_hunk_lines = (0, 0);
+            if !self.skip_file {
+                self._handle_diff_header_header_line(self.source == Source::DiffUnified)?;
+                self.handled_diff_header_header_line_file_pair
+                    .clone_from(&self.current_file_pair);
+                if let Some(line_numbers_data) = &mut self.painter.line_numbers_data {
+                    line_numbers_data.initialize_file(self.commit.clone());
+                }
+                self.start_collapsed_file();
+                if self.collapsed_file.is_none() {
+                    self.start_file_summary();
+                    self.start_extra_context();
+                    self.start_breadcrumbs();
+                    self.start_inline_blame();
+                }
+            }
         }
         Ok(handled_line)
     }
 
+    /// Return whether the current file passes the --include, --exclude and --only filters.
+    pub fn should_display_file(&self) -> bool {
+        let paths = [&self.minus_file, &self.plus_file]
+            .iter()
+            .filter(|path| path.as_str() != "/dev/null")
+            .map(|path| path.trim_end_matches(" (binary file)"))
+            .collect::<Vec<_>>();
+        let file_event = match (self.minus_file.as_str(), self.plus_file.as_str()) {
+            (minus_file, plus_file) if minus_file == plus_file => FileEvent::Change,
+            (_, "/dev/null") => FileEvent::Removed,
+            ("/dev/null", _) => FileEvent::Added,
+            // Files with different names, without git's "rename from" or "copy from" header line,
+            // are modified files, e.g. in `diff -u old new` output.
+            _ => self.rename_or_copy.clone().unwrap_or(FileEvent::Change),
+        };
+        !paths.iter().any(|path| self.config.exclude.is_match(path))
+            && self
+                .config
+                .include
+                .as_ref()
+                .is_none_or(|include| paths.iter().any(|path| include.is_match(path)))
+            && self
+                .config
+                .only
+                .as_ref()
+                .is_none_or(|only| only.contains(&file_event))
+    }
+
+    /// Skip the lines of a file which is not displayed, until the next file or commit starts.
+    pub fn handle_skipped_file_line(&mut self) -> bool {
+        if !self.skip_file || self.source != Source::DiffUnified {
+            return self.skip_file;
+        }
+        // In `diff -u` output the next file starts with its "--- " header line, but within a hunk
+        // such a line may be a removed line, e.g. a "-- " SQL or Lua comment.
+        let (minus_lines, plus_lines) = &mut self.skipped_hunk_lines;
+        if *minus_lines == 0 && *plus_lines == 0 {
+            if self.line.starts_with("--- ") {
+                self.skip_file = false;
+            } else if let Some(hunk_lines) = parse_unified_hunk_lengths(&self.line) {
+                self.skipped_hunk_lines = hunk_lines;
+            }
+        } else {
+            match self.line.chars().next() {
+                Some('-') => *minus_lines = minus_lines.saturating_sub(1),
+                Some('+') => *plus_lines = plus_lines.saturating_sub(1),
+                Some('\\') => {}
+                _ => {
+                    *minus_lines = minus_lines.saturating_sub(1);
+                    *plus_lines = plus_lines.saturating_sub(1);
+                }
+            }
+        }
+        self.skip_file
+    }
+
     #[inline]
     fn test_diff_header_file_operation_line(&self) -> bool {
         (matches!(self.state, State::DiffHeader(_)) || self.source == Source::DiffUnified)
@@ -197,8 +290,10 @@ impl StateMachine<'_> {
             comparing,
             &self.minus_file_event,
             &self.plus_file_event,
+            self.commit.as_deref(),
             self.config,
         );
+        self.add_toc_entry(&line);
         // FIXME: no support for 'raw'
         write_generic_diff_header_header_line(
             &line,
@@ -218,6 +313,10 @@ impl StateMachine<'_> {
         if !self.test_pending_line_with_diff_name() {
             return Ok(());
         }
+        if !self.should_display_file() {
+            self.mode_info.clear();
+            return Ok(());
+        }
 
         if !self.mode_info.is_empty() {
             let format_label = |label: &str| {
@@ -234,6 +333,8 @@ impl StateMachine<'_> {
                 (true, Some(absolute_path)) => features::hyperlinks::format_osc8_file_hyperlink(
                     absolute_path,
                     None,
+                    None,
+                    self.commit.as_deref(),
                     file,
                     self.config,
                 ),
@@ -242,6 +343,7 @@ impl StateMachine<'_> {
             let label = format_label(&self.config.file_modified_label);
             let name = get_repeated_file_path_from_diff_line(&self.diff_line).unwrap_or_default();
             let line = format!("{}{}", label, format_file(&name));
+            self.add_toc_entry(&line);
             write_generic_diff_header_header_line(
                 &line,
                 &line,
@@ -283,8 +385,9 @@ pub fn write_generic_diff_header_header_line(
         // Maintain 1-1 correspondence between input and output lines.
         writeln!(painter.writer)?;
     }
+    draw::write_semantic_mark(&mut painter.writer, NavigateLevel::File, config)?;
     draw_fn(
-        painter.writer,
+        &mut painter.writer,
         &format!("{}{}", line, if pad { " " } else { "" }),
         &format!("{}{}", raw_line, if pad { " " } else { "" }),
         mode_info,
@@ -401,6 +504,7 @@ pub fn get_file_change_description_from_file_paths(
     comparing: bool,
     minus_file_event: &FileEvent,
     plus_file_event: &FileEvent,
+    commit: Option<&str>,
     config: &Config,
 ) -> String {
     let format_label = |label: &str| {
@@ -429,6 +533,8 @@ pub fn get_file_change_description_from_file_paths(
                 (true, Some(absolute_path)) => features::hyperlinks::format_osc8_file_hyperlink(
                     absolute_path,
                     None,
+                    None,
+                    commit,
                     &formatted_file,
                     config,
                 ),
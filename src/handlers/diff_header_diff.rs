@@ -14,6 +14,8 @@ impl StateMachine<'_> {
         }
         self.painter.paint_buffered_minus_and_plus_lines();
         self.painter.emit()?;
+        self.finish_file()?;
+        self.skip_file = false;
         self.state =
             if self.line.starts_with("diff --cc ") || self.line.starts_with("diff --combined ") {
                 // We will determine the number of parents when we see the hunk header.
@@ -26,6 +28,8 @@ impl StateMachine<'_> {
             };
         self.handle_pending_line_with_diff_name()?;
         self.handled_diff_header_header_line_file_pair = None;
+        self.minus_blob_id = None;
+        self.plus_blob_id = None;
         self.diff_line.clone_from(&self.line);
 
         // Pre-fill header fields from the diff line. For added, removed or renamed files
@@ -38,6 +42,7 @@ impl StateMachine<'_> {
         self.plus_file.clone_from(&name);
         self.minus_file_event = FileEvent::Change;
         self.plus_file_event = FileEvent::Change;
+        self.rename_or_copy = None;
         self.current_file_pair = Some((self.minus_file.clone(), self.plus_file.clone()));
 
         if !self.should_skip_line() {
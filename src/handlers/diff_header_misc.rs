@@ -29,6 +29,7 @@ impl StateMachine<'_> {
                 return Ok(true);
             }
 
+            let binary_file_contents = self.get_binary_file_contents();
             if self.minus_file != "/dev/null" {
                 relativize_path_maybe(&mut self.minus_file, self.config);
                 self.minus_file.push_str(" (binary file)");
@@ -37,6 +38,9 @@ impl StateMachine<'_> {
                 relativize_path_maybe(&mut self.plus_file, self.config);
                 self.plus_file.push_str(" (binary file)");
             }
+            if let Some(contents) = binary_file_contents {
+                self.write_binary_file_summary(contents)?;
+            }
             return Ok(true);
         }
 
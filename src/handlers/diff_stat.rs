@@ -1,6 +1,7 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::borrow::Cow;
+use std::io::Write;
 
 use crate::config::Config;
 use crate::delta::{State, StateMachine};
@@ -21,9 +22,12 @@ impl StateMachine<'_> {
         let mut handled_line = false;
         if self.config.relative_paths {
             if let Some(cwd) = self.config.cwd_relative_to_repo_root.as_deref() {
-                if let Some(replacement_line) =
-                    relativize_path_in_diff_stat_line(&self.raw_line, cwd, self.config)
-                {
+                if let Some(replacement_line) = relativize_path_in_diff_stat_line(
+                    &self.raw_line,
+                    cwd,
+                    self.commit.as_deref(),
+                    self.config,
+                ) {
                     self.painter.emit()?;
                     writeln!(self.painter.writer, "{replacement_line}")?;
                     handled_line = true
@@ -46,6 +50,7 @@ lazy_static! {
 pub fn relativize_path_in_diff_stat_line(
     line: &str,
     cwd_relative_to_repo_root: &str,
+    commit: Option<&str>,
     config: &Config,
 ) -> Option<String> {
     let caps = DIFF_STAT_LINE_REGEX.captures(line)?;
@@ -61,6 +66,8 @@ pub fn relativize_path_in_diff_stat_line(
         (true, Some(absolute_path)) => features::hyperlinks::format_osc8_file_hyperlink(
             absolute_path,
             None,
+            None,
+            commit,
             relative_path,
             config,
         ),
@@ -3,7 +3,10 @@ use std::io::Write;
 
 use crate::ansi;
 use crate::cli::Width;
+use crate::config::Config;
+use crate::features::navigate::NavigateLevel;
 use crate::style::{DecorationStyle, Style};
+use crate::utils::builtin_pager;
 
 fn paint_text(text_style: Style, text: &str, addendum: &str) -> String {
     if addendum.is_empty() {
@@ -15,6 +18,26 @@ fn paint_text(text_style: Style, text: &str, addendum: &str) -> String {
     }
 }
 
+/// The OSC 133 mark of the start of a prompt, which terminals let the user jump to.
+const SEMANTIC_MARK: &str = "\x1b]133;A\x1b\\";
+
+/// Write a semantic mark before a commit, file or hunk header, if requested, so that terminals
+/// supporting OSC 133 can jump between headers. The built-in pager and --output-sections have
+/// their own marks, which also tell which kind of header follows.
+pub fn write_semantic_mark(
+    writer: &mut dyn Write,
+    level: NavigateLevel,
+    config: &Config,
+) -> std::io::Result<()> {
+    if config.semantic_marks {
+        write!(writer, "{SEMANTIC_MARK}")?;
+    }
+    if config.section_markers {
+        write!(writer, "{}", builtin_pager::format_section_marker(level))?;
+    }
+    Ok(())
+}
+
 pub type DrawFunction = dyn FnMut(
     &mut dyn Write,
     &str,
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::io::Write;
 
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -28,6 +29,12 @@ pub struct GrepLine<'b> {
 }
 
 impl GrepLine<'_> {
+    /// Return the column of the first match in the line, counting characters from 1, if known.
+    fn column(&self) -> Option<usize> {
+        let (start, _) = self.submatches.as_ref()?.first()?;
+        Some(self.code.get(..*start)?.chars().count() + 1)
+    }
+
     fn expand_tabs(&mut self, tab_cfg: &tabs::TabCfg) {
         let old_len = self.code.len();
         self.code = tabs::expand(&self.code, tab_cfg).into();
@@ -168,6 +175,8 @@ impl StateMachine<'_> {
                 &mut self.painter,
                 &self.line,
                 &grep_line.path,
+                None,
+                None,
                 self.config.ripgrep_header_style.decoration_style,
                 &self.config.grep_file_style,
                 &self.config.grep_line_number_style,
@@ -183,6 +192,7 @@ impl StateMachine<'_> {
             writeln!(self.painter.writer, "--")?;
         }
         // Emit the actual grep hit line
+        let column = grep_line.column();
         let code_style_sections = match (&grep_line.line_type, &grep_line.submatches) {
             (LineType::Match, Some(_)) => {
                 // We expand tabs at this late stage because
@@ -227,6 +237,8 @@ impl StateMachine<'_> {
             &mut self.painter,
             &self.line,
             &grep_line.path,
+            None,
+            column,
             crate::style::DecorationStyle::NoDecoration,
             &self.config.grep_file_style,
             &self.config.grep_line_number_style,
@@ -257,6 +269,8 @@ impl StateMachine<'_> {
                     &mut self.painter,
                     &self.line,
                     &grep_line.path,
+                    None,
+                    None,
                     self.config.classic_grep_header_style.decoration_style,
                     &self.config.classic_grep_header_file_style,
                     &self.config.grep_line_number_style,
@@ -311,7 +325,9 @@ impl StateMachine<'_> {
             "{}",
             paint::paint_file_path_with_line_number(
                 grep_line.line_number,
+                grep_line.column(),
                 &grep_line.path,
+                None,
                 OUTPUT_CONFIG.pad_line_number,
                 separator,
                 true,
@@ -1291,4 +1307,20 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn test_grep_line_column() {
+        let line = r#"{"type":"match","data":{"path":{"text":"src/cli.rs"},"lines":{"text":"    é fn from_clap(\n"},"line_number":3,"absolute_offset":0,"submatches":[{"match":{"text":"fn"},"start":7,"end":9}]}}"#;
+        assert_eq!(parse_grep_line(line).unwrap().column(), Some(7));
+
+        let grep_line = GrepLine {
+            grep_type: GrepType::Classic,
+            path: "src/cli.rs".into(),
+            line_number: Some(3),
+            line_type: LineType::Match,
+            code: "    é fn from_clap(".into(),
+            submatches: None,
+        };
+        assert_eq!(grep_line.column(), None);
+    }
 }
@@ -75,6 +75,21 @@ impl StateMachine<'_> {
         {
             self.painter.paint_buffered_minus_and_plus_lines();
         }
+        if self.collapsed_file.is_some() {
+            // The lines of a collapsed file are only counted.
+            let state = new_line_state(&self.line, &self.raw_line, &self.state, self.config);
+            if matches!(state, Some(HunkMinus(_, _) | HunkZero(_, _))) {
+                self.minus_line_counter.count_line();
+            }
+            self.state = state.unwrap_or(HunkZero(Unified, None));
+            if let Some(collapsed_file) = &mut self.collapsed_file {
+                collapsed_file.count_line(&self.state);
+            }
+            if let Some(toc) = &mut self.toc {
+                toc.count_line(&self.state);
+            }
+            return Ok(true);
+        }
         if let State::HunkHeader(_, parsed_hunk_header, line, raw_line) = &self.state.clone() {
             self.emit_hunk_header_line(parsed_hunk_header, line, raw_line)?;
         }
@@ -127,6 +142,15 @@ impl StateMachine<'_> {
                 State::HunkZero(Unified, None)
             }
         };
+        if let Some(file_summary) = &mut self.file_summary {
+            file_summary.push_line(&self.line, &self.state);
+        }
+        if let Some(lfs_pointer_hunk) = &mut self.lfs_pointer_hunk {
+            lfs_pointer_hunk.push_line(&self.line, &self.state);
+        }
+        if let Some(toc) = &mut self.toc {
+            toc.count_line(&self.state);
+        }
         self.painter.emit()?;
         Ok(true)
     }
@@ -20,12 +20,15 @@
 // ```
 use std::convert::TryInto;
 use std::fmt::Write as FmtWrite;
+use std::io::Write;
 
 use super::draw;
 use crate::config::{
     Config, HunkHeaderIncludeCodeFragment, HunkHeaderIncludeFilePath, HunkHeaderIncludeLineNumber,
 };
 use crate::delta::{self, DiffType, InMergeConflict, MergeParents, State, StateMachine};
+use crate::features::extra_context::{self, HunkContext};
+use crate::features::navigate::NavigateLevel;
 use crate::paint::{self, BgShouldFill, Painter, StyleSectionSpecifier};
 use crate::style::{DecorationStyle, Style};
 use lazy_static::lazy_static;
@@ -136,6 +139,11 @@ impl StateMachine<'_> {
                 }
             }
 
+            if let Some(file_summary) = &mut self.file_summary {
+                file_summary.start_hunk(&parsed_hunk_header.line_numbers_and_hunk_lengths);
+            }
+            self.start_lfs_pointer_hunk(&parsed_hunk_header.line_numbers_and_hunk_lengths)?;
+
             self.state = HunkHeader(
                 diff_type,
                 parsed_hunk_header,
@@ -155,14 +163,44 @@ impl StateMachine<'_> {
         raw_line: &str,
     ) -> std::io::Result<bool> {
         self.painter.paint_buffered_minus_and_plus_lines();
-        self.painter.set_highlighter();
-        self.painter.emit()?;
+        self.painter.paint_deferred_zero_lines();
 
         let ParsedHunkHeader {
             code_fragment,
             line_numbers_and_hunk_lengths,
         } = parsed_hunk_header;
 
+        let extra_context_before_hunk = match self
+            .extra_context
+            .as_mut()
+            .and_then(|extra_context| extra_context.hunk_context(line_numbers_and_hunk_lengths))
+        {
+            Some(HunkContext::Merged(line_numbers)) => {
+                self.paint_extra_context_lines(line_numbers)?;
+                return Ok(true);
+            }
+            Some(HunkContext::Separate {
+                after_previous_hunk,
+                before_hunk,
+            }) => {
+                self.paint_extra_context_lines(after_previous_hunk)?;
+                before_hunk
+            }
+            None => 0..0,
+        };
+        let line_numbers_and_hunk_lengths = &extra_context::extend_hunk_start(
+            line_numbers_and_hunk_lengths,
+            extra_context_before_hunk.len(),
+        );
+
+        self.painter.set_highlighter();
+        self.painter.emit()?;
+
+        let code_fragment = &self.code_fragment_with_breadcrumb(
+            code_fragment,
+            extra_context::hunk_start(line_numbers_and_hunk_lengths),
+        );
+
         if self.config.line_numbers {
             self.painter
                 .line_numbers_data
@@ -181,6 +219,7 @@ impl StateMachine<'_> {
             if !self.config.color_only {
                 writeln!(self.painter.writer)?;
             }
+            draw::write_semantic_mark(&mut self.painter.writer, NavigateLevel::Hunk, self.config)?;
 
             write_line_of_code_with_optional_path_and_line_number(
                 code_fragment,
@@ -193,6 +232,8 @@ impl StateMachine<'_> {
                 } else {
                     &self.plus_file
                 },
+                self.commit.as_deref(),
+                None,
                 self.config.hunk_header_style.decoration_style,
                 &self.config.hunk_header_file_style,
                 &self.config.hunk_header_line_number_style,
@@ -205,6 +246,7 @@ impl StateMachine<'_> {
             )?;
         };
         self.painter.set_highlighter();
+        self.paint_extra_context_lines(extra_context_before_hunk)?;
         Ok(true)
     }
 }
@@ -229,6 +271,17 @@ lazy_static! {
     .unwrap();
 }
 
+/// Return the numbers of minus and plus lines of a unified diff hunk, given its header.
+pub fn parse_unified_hunk_lengths(line: &str) -> Option<(usize, usize)> {
+    match parse_hunk_header(line)?
+        .line_numbers_and_hunk_lengths
+        .as_slice()
+    {
+        &[(_, minus_lines), (_, plus_lines)] => Some((minus_lines, plus_lines)),
+        _ => None,
+    }
+}
+
 /// Given input like
 /// "@@ -74,15 +74,14 @@ pub fn delta("
 /// Return " pub fn delta(" and a vector of (line_number, hunk_length) tuples.
@@ -274,8 +327,9 @@ fn write_hunk_header_raw(
     if config.hunk_header_style.decoration_style != DecorationStyle::NoDecoration {
         writeln!(painter.writer)?;
     }
+    draw::write_semantic_mark(&mut painter.writer, NavigateLevel::Hunk, config)?;
     draw_fn(
-        painter.writer,
+        &mut painter.writer,
         &format!("{}{}", line, if pad { " " } else { "" }),
         &format!("{}{}", raw_line, if pad { " " } else { "" }),
         "",
@@ -294,6 +348,8 @@ pub fn write_line_of_code_with_optional_path_and_line_number(
     painter: &mut Painter,
     line: &str,
     plus_file: &str,
+    commit: Option<&str>,
+    column: Option<usize>,
     decoration_style: DecorationStyle,
     file_style: &Style,
     line_number_style: &Style,
@@ -319,7 +375,9 @@ pub fn write_line_of_code_with_optional_path_and_line_number(
     let plus_line_number = line_numbers_and_hunk_lengths[line_numbers_and_hunk_lengths.len() - 1].0;
     let file_with_line_number = paint_file_path_with_line_number(
         Some(plus_line_number),
+        column,
         plus_file,
+        commit,
         file_style,
         line_number_style,
         include_file_path,
@@ -339,7 +397,7 @@ pub fn write_line_of_code_with_optional_path_and_line_number(
             config,
         );
         draw_fn(
-            painter.writer,
+            &mut painter.writer,
             &painter.output_buffer,
             &painter.output_buffer,
             "",
@@ -356,7 +414,9 @@ pub fn write_line_of_code_with_optional_path_and_line_number(
 #[allow(clippy::too_many_arguments)]
 fn paint_file_path_with_line_number(
     line_number: Option<usize>,
+    column: Option<usize>,
     plus_file: &str,
+    commit: Option<&str>,
     file_style: &Style,
     line_number_style: &Style,
     include_file_path: &HunkHeaderIncludeFilePath,
@@ -380,7 +440,9 @@ fn paint_file_path_with_line_number(
 
     paint::paint_file_path_with_line_number(
         line_number,
+        column,
         plus_file,
+        commit,
         false,
         separator,
         false,
@@ -522,7 +584,9 @@ pub mod tests {
 
         let result = paint_file_path_with_line_number(
             Some(3),
+            None,
             "some-file",
+            None,
             &config.hunk_header_style,
             &config.hunk_header_line_number_style,
             &config.hunk_header_style_include_file_path,
@@ -552,7 +616,9 @@ pub mod tests {
 
         let result = paint_file_path_with_line_number(
             Some(3),
+            None,
             &relative_path.to_string_lossy(),
+            None,
             &config.hunk_header_style,
             &config.hunk_header_line_number_style,
             &config.hunk_header_style_include_file_path,
@@ -585,7 +651,9 @@ pub mod tests {
 
         let result = paint_file_path_with_line_number(
             Some(3),
+            None,
             "some-file",
+            None,
             &config.hunk_header_style,
             &config.hunk_header_line_number_style,
             &config.hunk_header_style_include_file_path,
@@ -616,7 +684,9 @@ pub mod tests {
 
         let result = paint_file_path_with_line_number(
             Some(3),
+            None,
             "some-file",
+            None,
             &config.hunk_header_style,
             &config.hunk_header_line_number_style,
             &config.hunk_header_style_include_file_path,
@@ -640,7 +710,9 @@ pub mod tests {
 
         let result = paint_file_path_with_line_number(
             Some(3),
+            None,
             "δ some-file",
+            None,
             &config.hunk_header_style,
             &config.hunk_header_line_number_style,
             &config.hunk_header_style_include_file_path,
@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::ops::{Index, IndexMut};
 
 use itertools::Itertools;
@@ -7,6 +8,7 @@ use super::draw;
 use crate::cli;
 use crate::config::{self, delta_unreachable};
 use crate::delta::{DiffType, InMergeConflict, MergeParents, State, StateMachine};
+use crate::features::navigate::NavigateLevel;
 use crate::minusplus::MinusPlus;
 use crate::paint::{self, prepare};
 use crate::style::Style;
@@ -137,6 +139,11 @@ impl StateMachine<'_> {
         use State::*;
         self.painter.emit()?;
 
+        draw::write_semantic_mark(
+            &mut self.painter.writer,
+            NavigateLevel::Conflict,
+            self.config,
+        )?;
         write_merge_conflict_bar(
             &self.config.merge_conflict_begin_symbol,
             &mut self.painter,
@@ -197,7 +204,7 @@ fn write_diff_header(
         derived_commit_name.as_deref().unwrap_or("?").to_string()
     };
     draw_fn(
-        painter.writer,
+        &mut painter.writer,
         &text,
         &text,
         "",
@@ -302,7 +309,9 @@ impl MergeConflictCommitNames {
 #[cfg(test)]
 mod tests {
     use crate::ansi::strip_ansi_codes;
+    use crate::features::navigate::NavigateLevel;
     use crate::tests::integration_test_utils;
+    use crate::utils::builtin_pager;
 
     #[test]
     fn test_toy_merge_conflict_no_context() {
@@ -320,6 +329,15 @@ ancestor ⟶   HEAD │
         assert!(output.contains("\n▲▲▲▲▲▲▲▲▲▲▲▲▲▲▲▲▲▲"));
     }
 
+    #[test]
+    fn test_merge_conflict_section_marker() {
+        let mut config = integration_test_utils::make_config_from_args(&[]);
+        config.section_markers = true;
+        let output = integration_test_utils::run_delta(GIT_TOY_MERGE_CONFLICT_NO_CONTEXT, &config);
+        let conflict_marker = builtin_pager::format_section_marker(NavigateLevel::Conflict);
+        assert!(output.contains(&format!("\n{conflict_marker}▼▼▼▼▼▼▼▼▼▼▼▼▼▼▼▼▼▼")));
+    }
+
     #[test]
     fn test_real_merge_conflict() {
         let config = integration_test_utils::make_config_from_args(&[]);
@@ -1,5 +1,6 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::io::Write;
 
 use crate::delta::{State, StateMachine};
 
@@ -36,6 +36,7 @@ use crate::delta::delta;
 use crate::subcommands::{SubCmdKind, SubCommand};
 use crate::utils::bat::assets::list_languages;
 use crate::utils::bat::output::{OutputType, PagingMode};
+use crate::utils::rendered_output::RenderedOutputWriter;
 
 pub fn fatal<T>(errmsg: T) -> !
 where
@@ -115,6 +116,7 @@ pub fn run_app(
         Some(subcommands::show_syntax_themes::show_syntax_themes())
     } else if opt.show_themes {
         Some(subcommands::show_themes::show_themes(
+            &env,
             opt.dark,
             opt.light,
             opt.computed.color_mode,
@@ -155,12 +157,35 @@ pub fn run_app(
     };
     let mut output_type =
         OutputType::from_mode(&env, paging_mode, config.pager.clone(), &pager_cfg).unwrap();
+    let mut rendered_output = config.output.as_ref().map(|path| {
+        RenderedOutputWriter::create(path, config.output_sections).unwrap_or_else(|error| {
+            fatal(format!(
+                "Failed to create output file {}: {error}",
+                path.display()
+            ))
+        })
+    });
     let mut writer: &mut dyn Write = if paging_mode == PagingMode::Capture {
         &mut capture_output.unwrap()
+    } else if let Some(rendered_output) = &mut rendered_output {
+        rendered_output
     } else {
         output_type.handle().unwrap()
     };
 
+    if let Some(path) = &config.replay {
+        let result = utils::rendered_output::replay(path, &mut writer, &config);
+        // An error of the built-in pager takes precedence: it stops the writing of the output.
+        return match output_type.finish().and(result) {
+            Ok(()) => Ok(0),
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(0),
+            Err(error) => {
+                eprintln!("Failed to replay {}: {error}", path.display());
+                Ok(config.error_exit_code)
+            }
+        };
+    }
+
     let subcmd = match call {
         Call::DeltaDiff(_, minus, plus) => {
             match subcommands::diff::build_diff_cmd(&minus, &plus, &config) {
@@ -187,6 +212,11 @@ pub fn run_app(
         }
 
         let res = delta(io::stdin().lock().byte_lines(), &mut writer, &config);
+        let res = output_type.finish().and(res).and(
+            rendered_output
+                .as_mut()
+                .map_or(Ok(()), RenderedOutputWriter::finish),
+        );
 
         if let Err(error) = res {
             match error.kind() {
@@ -233,6 +263,11 @@ pub fn run_app(
         let cmd_stdout_buf = io::BufReader::new(cmd_stdout);
 
         let res = delta(cmd_stdout_buf.byte_lines(), &mut writer, &config);
+        let res = output_type.finish().and(res).and(
+            rendered_output
+                .as_mut()
+                .map_or(Ok(()), RenderedOutputWriter::finish),
+        );
 
         if let Err(error) = res {
             let _ = cmd.wait(); // for clippy::zombie_processes
@@ -22,7 +22,11 @@ use ProvenancedOptionValue::*;
 //        builtin feature named `feature`, then stop searching and return that value.
 //        Otherwise, record the default value and continue searching.
 //
-// 3. Return the last default value that was encountered.
+// 3. Delta's config file lies beneath git config, so if no value was found in git config, repeat
+//    steps 1 and 2 using the top level of the config file instead of the [delta] section, and its
+//    tables (or theme files) instead of [delta "`feature`"] sections.
+//
+// 4. Return the last default value that was encountered.
 pub fn get_option_value<T>(
     option_name: &str,
     builtin_features: &HashMap<String, features::BuiltinFeature>,
@@ -71,29 +75,78 @@ pub trait GetOptionValue {
         Self: Into<OptionValue>,
     {
         if let Some(git_config) = git_config {
-            if let Some(value) = git_config.get::<Self>(&format!("delta.{option_name}")) {
+            if let Some(value) = git_config.get_from_git::<Self>(&format!("delta.{option_name}")) {
+                return Some(value);
+            }
+        }
+        let features: Vec<&str> = match &opt.features {
+            Some(features) => features.split_whitespace().rev().collect(),
+            None => Vec::new(),
+        };
+        for feature in &features {
+            match Self::get_provenanced_value_for_feature(
+                option_name,
+                feature,
+                builtin_features,
+                opt,
+                git_config,
+            ) {
+                Some(GitConfigValue(value)) | Some(ConfigFileValue(value)) => {
+                    return Some(value.into());
+                }
+                // A builtin feature's value; the config file may set the option instead.
+                Some(DefaultValue(_)) => break,
+                None => {}
+            }
+        }
+        if let Some(git_config) = git_config {
+            if let Some(value) =
+                git_config.get_from_config_files::<Self>(&format!("delta.{option_name}"))
+            {
                 return Some(value);
             }
         }
-        if let Some(features) = &opt.features {
-            for feature in features.split_whitespace().rev() {
-                match Self::get_provenanced_value_for_feature(
-                    option_name,
-                    feature,
-                    builtin_features,
-                    opt,
-                    git_config,
-                ) {
-                    Some(GitConfigValue(value)) | Some(DefaultValue(value)) => {
-                        return Some(value.into());
-                    }
-                    None => {}
+        for feature in &features {
+            match Self::get_config_file_value_for_feature(option_name, feature, git_config).or_else(
+                || {
+                    Self::get_provenanced_value_for_feature(
+                        option_name,
+                        feature,
+                        builtin_features,
+                        opt,
+                        git_config,
+                    )
+                },
+            ) {
+                Some(GitConfigValue(value))
+                | Some(ConfigFileValue(value))
+                | Some(DefaultValue(value)) => {
+                    return Some(value.into());
                 }
+                None => {}
             }
         }
         None
     }
 
+    /// Return the value associated with `option_name` under feature name `feature` in delta's
+    /// config file or theme files.
+    fn get_config_file_value_for_feature(
+        option_name: &str,
+        feature: &str,
+        git_config: &Option<git_config::GitConfig>,
+    ) -> Option<ProvenancedOptionValue>
+    where
+        Self: Sized,
+        Self: GitConfigGet,
+        Self: Into<OptionValue>,
+    {
+        let value = git_config
+            .as_ref()?
+            .get_from_config_files::<Self>(&format!("delta.{feature}.{option_name}"))?;
+        Some(ConfigFileValue(value.into()))
+    }
+
     /// Return the value, or default value, associated with `option_name` under feature name
     /// `feature`. This may refer to a custom feature, or a builtin feature, or both. Only builtin
     /// features have defaults. See `GetOptionValue::get_option_value`.
@@ -110,7 +163,9 @@ pub trait GetOptionValue {
         Self: Into<OptionValue>,
     {
         if let Some(git_config) = git_config {
-            if let Some(value) = git_config.get::<Self>(&format!("delta.{feature}.{option_name}")) {
+            if let Some(value) =
+                git_config.get_from_git::<Self>(&format!("delta.{feature}.{option_name}"))
+            {
                 return Some(GitConfigValue(value.into()));
             }
         }
@@ -135,6 +190,7 @@ pub mod tests {
 
     use crate::cli::Opt;
     use crate::env::DeltaEnv;
+    use crate::git_config::GitConfig;
     use crate::options::get::get_themes;
     use crate::tests::integration_test_utils;
 
@@ -354,4 +410,64 @@ pub mod tests {
 
         remove_file(git_config_path).unwrap();
     }
+
+    #[test]
+    fn test_get_themes_from_theme_files() {
+        let config_home = std::env::temp_dir().join("delta__test_get_themes_from_theme_files");
+        let themes_dir = config_home.join("delta").join("themes");
+        std::fs::create_dir_all(&themes_dir).unwrap();
+        std::fs::write(
+            themes_dir.join("kingfisher.toml"),
+            r#"
+author = "https://github.com/someone"
+dark = true
+syntax-theme = "Dracula"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            themes_dir.join("birds.gitconfig"),
+            r#"
+[delta "heron"]
+    light = true
+    syntax-theme = GitHub
+"#,
+        )
+        .unwrap();
+        std::fs::write(themes_dir.join("README.md"), "Not a theme").unwrap();
+        let env = DeltaEnv {
+            config_home: Some(config_home.clone()),
+            ..DeltaEnv::default()
+        };
+
+        let (git_config, _) = GitConfig::with_config_files(None, &env);
+        assert_eq!(
+            git_config
+                .as_ref()
+                .unwrap()
+                .get::<String>("delta.kingfisher.author"),
+            Some("https://github.com/someone".to_string())
+        );
+        assert_eq!(get_themes(git_config), ["heron", "kingfisher"]);
+
+        let opt = integration_test_utils::make_options_from_args_and_git_config_with_custom_env(
+            env.clone(),
+            &["--features", "kingfisher"],
+            None,
+            None,
+        );
+        assert!(opt.dark);
+        assert_eq!(opt.syntax_theme, Some("Dracula".to_string()));
+
+        let opt = integration_test_utils::make_options_from_args_and_git_config_with_custom_env(
+            env,
+            &["--features", "heron"],
+            None,
+            None,
+        );
+        assert!(opt.light);
+        assert_eq!(opt.syntax_theme, Some("GitHub".to_string()));
+
+        std::fs::remove_dir_all(config_home).unwrap();
+    }
 }
@@ -10,8 +10,10 @@ pub enum OptionValue {
 }
 
 /// An OptionValue, tagged according to its provenance/semantics.
+#[allow(clippy::enum_variant_names)]
 pub enum ProvenancedOptionValue {
     GitConfigValue(OptionValue),
+    ConfigFileValue(OptionValue),
     DefaultValue(OptionValue),
 }
 
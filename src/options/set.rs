@@ -45,6 +45,8 @@ macro_rules! set_options {
                 "diff-so-fancy", // Does not exist as a flag on config
                 "detect-dark-light", // Does not exist as a flag on config
                 "features",  // Processed differently
+                "output", // Command line only
+                "replay", // Command line only
                 // Set prior to the rest
                 "no-gitconfig",
                 "dark",
@@ -127,6 +129,9 @@ pub fn set_options(
 
     set_options!(
         [
+            binary_hexdump_max_size,
+            binary_summary,
+            binary_summary_style,
             blame_code_style,
             blame_format,
             blame_separator_format,
@@ -134,6 +139,10 @@ pub fn set_options(
             blame_separator_style,
             blame_timestamp_format,
             blame_timestamp_output_format,
+            collapse_files,
+            collapse_generated_files,
+            collapse_lockfile_hunks,
+            collapsed_file_style,
             color_only,
             config,
             commit_decoration_style,
@@ -142,6 +151,8 @@ pub fn set_options(
             default_language,
             diff_args,
             diff_stat_align_width,
+            exclude,
+            extra_context,
             file_added_label,
             file_copied_label,
             file_decoration_style,
@@ -161,16 +172,28 @@ pub fn set_options(
             grep_match_line_style,
             grep_match_word_style,
             grep_separator_symbol,
+            hunk_header_breadcrumbs,
             hunk_header_decoration_style,
             hunk_header_file_style,
             hunk_header_line_number_style,
             hunk_header_style,
             hyperlinks,
             hyperlinks_commit_link_format,
+            hyperlinks_editor,
             hyperlinks_file_link_format,
+            hyperlinks_issue_link_format,
+            ignore_indentation,
+            include,
+            indentation_change_hint,
+            indentation_change_style,
+            inline_blame,
+            inline_blame_format,
+            inline_blame_style,
             inline_hint_style,
             inspect_raw_lines,
             keep_plus_minus_markers,
+            lfs_summary,
+            lfs_summary_style,
             line_buffer_size,
             map_styles,
             max_line_distance,
@@ -190,9 +213,12 @@ pub fn set_options(
             minus_non_emph_style,
             minus_non_emph_style,
             navigate,
+            navigate_levels,
             navigate_regex,
+            only,
             line_fill_method,
             line_numbers,
+            line_pairing,
             line_numbers_left_format,
             line_numbers_left_style,
             line_numbers_minus_style,
@@ -200,6 +226,9 @@ pub fn set_options(
             line_numbers_right_format,
             line_numbers_right_style,
             line_numbers_zero_style,
+            lockfile_summary,
+            lockfile_summary_style,
+            output_sections,
             pager,
             paging_mode,
             parse_ansi,
@@ -211,15 +240,22 @@ pub fn set_options(
             plus_non_emph_style,
             raw,
             relative_paths,
+            semantic_marks,
             show_colors,
             show_themes,
             side_by_side,
+            side_by_side_min_width,
+            side_by_side_mode,
+            structural_summary,
+            structural_summary_style,
             wrap_max_lines,
             wrap_right_prefix_symbol,
             wrap_right_percent,
             wrap_right_symbol,
             wrap_left_symbol,
             tab_width,
+            toc,
+            toc_style,
             tokenization_regex,
             true_color,
             whitespace_error_style,
@@ -242,6 +278,16 @@ pub fn set_options(
         cli::InspectRawLines::from_str(&opt.inspect_raw_lines).unwrap();
     opt.computed.paging_mode = parse_paging_mode(&opt.paging_mode);
 
+    if opt.side_by_side && opt.side_by_side_mode == "auto" {
+        let width = match opt.computed.decorations_width {
+            cli::Width::Fixed(width) => width,
+            cli::Width::Variable => opt.computed.available_terminal_width,
+        };
+        if width < opt.side_by_side_min_width {
+            opt.side_by_side = false;
+        }
+    }
+
     // --color-only is used for interactive.diffFilter (git add -p). side-by-side, and
     // **-decoration-style cannot be used there (does not emit lines in 1-1 correspondence with raw git output).
     // See #274.
@@ -388,6 +434,9 @@ fn gather_features(
     if opt.hyperlinks {
         gather_builtin_features_recursively("hyperlinks", &mut features, builtin_features, opt);
     }
+    if opt.inline_blame {
+        gather_builtin_features_recursively("inline-blame", &mut features, builtin_features, opt);
+    }
     if opt.line_numbers {
         gather_builtin_features_recursively("line-numbers", &mut features, builtin_features, opt);
     }
@@ -673,6 +722,7 @@ pub mod tests {
     use std::fs::remove_file;
 
     use crate::cli;
+    use crate::env::DeltaEnv;
     use crate::tests::integration_test_utils;
     use crate::utils::bat::output::PagingMode;
 
@@ -828,6 +878,96 @@ pub mod tests {
         remove_file(git_config_path).unwrap();
     }
 
+    #[test]
+    fn test_options_can_be_set_in_config_file() {
+        let config_file_path =
+            std::env::temp_dir().join("delta__test_options_can_be_set_in_config_file.toml");
+        std::fs::write(
+            &config_file_path,
+            r#"
+features = "my-feature"
+line-numbers = true
+tabs = 3
+
+[my-feature]
+syntax-theme = "Monokai Extended"
+width = 80
+"#,
+        )
+        .unwrap();
+        let env = DeltaEnv {
+            delta_config: Some(config_file_path.clone()),
+            ..DeltaEnv::default()
+        };
+
+        let opt = integration_test_utils::make_options_from_args_and_git_config_with_custom_env(
+            env.clone(),
+            &[],
+            None,
+            None,
+        );
+        assert!(opt.line_numbers);
+        assert_eq!(opt.tab_width, 3);
+        assert_eq!(opt.syntax_theme, Some("Monokai Extended".to_string()));
+        assert_eq!(opt.computed.decorations_width, cli::Width::Fixed(80));
+
+        // Git config takes precedence over the config file.
+        let git_config_contents = b"
+[delta]
+    tabs = 5
+
+[delta \"my-feature\"]
+    width = 70
+";
+        let git_config_path =
+            std::env::temp_dir().join("delta__test_options_can_be_set_in_config_file.gitconfig");
+        let opt = integration_test_utils::make_options_from_args_and_git_config_with_custom_env(
+            env,
+            &[],
+            Some(git_config_contents),
+            git_config_path.to_str(),
+        );
+        assert!(opt.line_numbers);
+        assert_eq!(opt.tab_width, 5);
+        assert_eq!(opt.syntax_theme, Some("Monokai Extended".to_string()));
+        assert_eq!(opt.computed.decorations_width, cli::Width::Fixed(70));
+
+        remove_file(git_config_path).unwrap();
+        remove_file(config_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_git_config_features_take_precedence_over_config_file() {
+        let config_file_path = std::env::temp_dir()
+            .join("delta__test_git_config_features_take_precedence_over_config_file.toml");
+        std::fs::write(&config_file_path, "line-numbers = false\ntabs = 3\n").unwrap();
+        let env = DeltaEnv {
+            delta_config: Some(config_file_path.clone()),
+            ..DeltaEnv::default()
+        };
+        let git_config_contents = b"
+[delta]
+    features = my-feature
+
+[delta \"my-feature\"]
+    line-numbers = true
+";
+        let git_config_path = std::env::temp_dir()
+            .join("delta__test_git_config_features_take_precedence_over_config_file.gitconfig");
+
+        let opt = integration_test_utils::make_options_from_args_and_git_config_with_custom_env(
+            env,
+            &[],
+            Some(git_config_contents),
+            git_config_path.to_str(),
+        );
+        assert!(opt.line_numbers);
+        assert_eq!(opt.tab_width, 3);
+
+        remove_file(git_config_path).unwrap();
+        remove_file(config_file_path).unwrap();
+    }
+
     #[test]
     fn test_parse_width_specifier() {
         use super::parse_width_specifier;
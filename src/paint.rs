@@ -7,6 +7,7 @@ use itertools::Itertools;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::Style as SyntectStyle;
 use syntect::parsing::{SyntaxReference, SyntaxSet};
+use unicode_width::UnicodeWidthStr;
 
 use crate::config::{self, delta_unreachable, Config};
 use crate::delta::{DiffType, InMergeConflict, MergeParents, State};
@@ -14,6 +15,7 @@ use crate::features::hyperlinks;
 use crate::features::line_numbers::{self, LineNumbersData};
 use crate::features::side_by_side::ansifill;
 use crate::features::side_by_side::{self, PanelSide};
+use crate::handlers::hunk::is_word_diff;
 use crate::handlers::merge_conflict;
 use crate::minusplus::*;
 use crate::paint::superimpose_style_sections::superimpose_style_sections;
@@ -26,7 +28,10 @@ pub type LineSections<'a, S> = Vec<(S, &'a str)>;
 pub struct Painter<'p> {
     pub minus_lines: Vec<(String, State)>,
     pub plus_lines: Vec<(String, State)>,
-    pub writer: &'p mut dyn Write,
+    // Unchanged lines at the start of a hunk, held back while their line numbers cannot be
+    // computed, see `LineNumbersData::awaits_first_change`.
+    pub deferred_zero_lines: Vec<(String, State)>,
+    pub writer: OutputWriter<'p>,
     pub syntax: &'p SyntaxReference,
     pub highlighter: Option<HighlightLines<'p>>,
     pub config: &'p config::Config,
@@ -39,6 +44,53 @@ pub struct Painter<'p> {
     pub merge_conflict_commit_names: merge_conflict::MergeConflictCommitNames,
 }
 
+/// The destination of painted output. Output may be held back, e.g. while the hunks of a file are
+/// processed, so that something computed from them can be written before that output. Holds nest:
+/// output released by an inner hold and written again is held by the outer one.
+pub struct OutputWriter<'p> {
+    writer: &'p mut dyn Write,
+    held: Vec<Vec<u8>>,
+}
+
+impl<'p> OutputWriter<'p> {
+    pub fn new(writer: &'p mut dyn Write) -> Self {
+        Self {
+            writer,
+            held: Vec::new(),
+        }
+    }
+
+    /// Hold back subsequent output until `release` is called.
+    pub fn hold(&mut self) {
+        self.held.push(Vec::new());
+    }
+
+    /// Stop holding back output (the innermost hold), and return what has been held.
+    pub fn release(&mut self) -> Vec<u8> {
+        self.held.pop().unwrap_or_default()
+    }
+}
+
+impl Write for OutputWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.held.last_mut() {
+            Some(held) => {
+                held.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            None => self.writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.held.is_empty() {
+            self.writer.flush()
+        } else {
+            Ok(())
+        }
+    }
+}
+
 // How the background of a line is filled up to the end
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum BgFillMethod {
@@ -91,10 +143,11 @@ impl<'p> Painter<'p> {
         Self {
             minus_lines: Vec::new(),
             plus_lines: Vec::new(),
+            deferred_zero_lines: Vec::new(),
             output_buffer: String::new(),
             syntax: default_syntax,
             highlighter: None,
-            writer,
+            writer: OutputWriter::new(writer),
             config,
             line_numbers_data,
             merge_conflict_lines: merge_conflict::MergeConflictLines::new(),
@@ -158,6 +211,12 @@ impl<'p> Painter<'p> {
         if self.minus_lines.is_empty() && self.plus_lines.is_empty() {
             return;
         }
+        if let Some(line_numbers_data) = &mut self.line_numbers_data {
+            if line_numbers_data.awaits_first_change() {
+                line_numbers_data.set_first_change(self.deferred_zero_lines.len());
+            }
+        }
+        self.paint_deferred_zero_lines();
         paint_minus_and_plus_lines(
             MinusPlus::new(&self.minus_lines, &self.plus_lines),
             &mut self.line_numbers_data,
@@ -170,6 +229,25 @@ impl<'p> Painter<'p> {
     }
 
     pub fn paint_zero_line(&mut self, line: &str, state: State) {
+        let awaits_first_change = self
+            .line_numbers_data
+            .as_ref()
+            .is_some_and(|line_numbers_data| line_numbers_data.awaits_first_change());
+        if awaits_first_change && !is_word_diff() {
+            self.deferred_zero_lines.push((line.to_string(), state));
+        } else {
+            self.paint_zero_line_now(line, state);
+        }
+    }
+
+    /// Paint the unchanged lines which were held back at the start of a hunk.
+    pub fn paint_deferred_zero_lines(&mut self) {
+        for (line, state) in std::mem::take(&mut self.deferred_zero_lines) {
+            self.paint_zero_line_now(&line, state);
+        }
+    }
+
+    fn paint_zero_line_now(&mut self, line: &str, state: State) {
         let lines = &[(line.to_string(), state.clone())];
         let syntax_style_sections =
             get_syntax_style_sections_for_lines(lines, self.highlighter.as_mut(), self.config);
@@ -200,6 +278,7 @@ impl<'p> Painter<'p> {
                 &syntax_style_sections,
                 diff_style_sections.as_slice(),
                 &[false],
+                None,
                 &mut self.output_buffer,
                 self.config,
                 &mut self.line_numbers_data.as_mut(),
@@ -217,6 +296,7 @@ impl<'p> Painter<'p> {
         syntax_style_sections: &[LineSections<'a, SyntectStyle>],
         diff_style_sections: &[LineSections<'a, Style>],
         lines_have_homolog: &[bool],
+        line_hints: Option<&[Option<String>]>, // text to display after a line, e.g. indentation change
         output_buffer: &mut String,
         config: &config::Config,
         line_numbers_data: &mut Option<&mut line_numbers::LineNumbersData>,
@@ -230,11 +310,12 @@ impl<'p> Painter<'p> {
         // 2. We must ensure that we fill rightwards with the appropriate
         //    non-emph background color. In that case we don't use the last
         //    style of the line, because this might be emph.
-        for ((((_, state), syntax_sections), diff_sections), &line_has_homolog) in lines
+        for (i, ((((_, state), syntax_sections), diff_sections), &line_has_homolog)) in lines
             .iter()
             .zip_eq(syntax_style_sections)
             .zip_eq(diff_style_sections)
             .zip_eq(lines_have_homolog)
+            .enumerate()
         {
             let (mut line, line_is_empty) = Painter::paint_line(
                 syntax_sections,
@@ -245,6 +326,9 @@ impl<'p> Painter<'p> {
                 painted_prefix(state.clone(), config),
                 config,
             );
+            if let Some(hint) = line_hints.and_then(|hints| hints[i].as_ref()) {
+                line.push_str(&config.inline_hint_style.paint(hint).to_string());
+            }
             let (bg_fill_mode, fill_style) =
                 Painter::get_should_right_fill_background_color_and_fill_style(
                     diff_sections,
@@ -302,6 +386,7 @@ impl<'p> Painter<'p> {
             &syntax_style_sections,
             &diff_style_sections,
             &[false],
+            None,
             &mut self.output_buffer,
             self.config,
             &mut None,
@@ -353,6 +438,15 @@ impl<'p> Painter<'p> {
             State::Blame(_) => diff_sections[0].0,
             _ => config.null_style,
         };
+        // A line differing from its homolog only in indentation is filled in its own style.
+        let fill_style = match (state, diff_sections) {
+            (State::HunkMinus(_, None) | State::HunkPlus(_, None), [(style, _)])
+                if config.ignore_indentation && *style == config.indentation_change_style =>
+            {
+                *style
+            }
+            _ => fill_style,
+        };
 
         match (
             fill_style.get_background_color().is_some(),
@@ -640,7 +734,20 @@ pub fn paint_minus_and_plus_lines(
         &lines_have_homolog[Plus],
         config,
     );
-    if config.side_by_side {
+    let indentation_change_hints = if config.ignore_indentation {
+        style_indentation_changes(&lines, &mut diff_style_sections, &line_alignment, config)
+    } else {
+        None
+    };
+    // In side-by-side auto mode, lines which are too long for their panel are not wrapped;
+    // instead the whole block is displayed in unified layout.
+    let paint_side_by_side = config.side_by_side
+        && !(config.side_by_side_auto
+            && line_numbers_data.as_ref().is_some_and(|data| {
+                let line_width = side_by_side::available_line_width(config, data);
+                side_by_side::has_long_lines(&lines, &line_width).0
+            }));
+    if paint_side_by_side {
         side_by_side::paint_minus_and_plus_lines_side_by_side(
             lines,
             syntax_style_sections,
@@ -659,6 +766,7 @@ pub fn paint_minus_and_plus_lines(
                 &syntax_style_sections[Minus],
                 &diff_style_sections[Minus],
                 &lines_have_homolog[Minus],
+                None,
                 output_buffer,
                 config,
                 &mut line_numbers_data.as_mut(),
@@ -672,6 +780,7 @@ pub fn paint_minus_and_plus_lines(
                 &syntax_style_sections[Plus],
                 &diff_style_sections[Plus],
                 &lines_have_homolog[Plus],
+                indentation_change_hints.as_deref(),
                 output_buffer,
                 config,
                 &mut line_numbers_data.as_mut(),
@@ -682,6 +791,68 @@ pub fn paint_minus_and_plus_lines(
     }
 }
 
+/// Style paired lines that differ only in their indentation with indentation-change-style. If
+/// requested, also return for each plus line a hint describing its change of indentation.
+fn style_indentation_changes<'a>(
+    lines: &MinusPlus<&'a Vec<(String, State)>>,
+    diff_style_sections: &mut MinusPlus<Vec<LineSections<'a, Style>>>,
+    line_alignment: &[(Option<usize>, Option<usize>)],
+    config: &config::Config,
+) -> Option<Vec<Option<String>>> {
+    let mut hints = vec![None; lines[Plus].len()];
+    for (minus_index, plus_index) in line_alignment
+        .iter()
+        .filter_map(|(minus_index, plus_index)| Some(((*minus_index)?, (*plus_index)?)))
+    {
+        let ((minus_line, minus_state), (plus_line, plus_state)) =
+            (&lines[Minus][minus_index], &lines[Plus][plus_index]);
+        if !matches!(minus_state, State::HunkMinus(_, None))
+            || !matches!(plus_state, State::HunkPlus(_, None))
+        {
+            continue;
+        }
+        let (minus_indentation, minus_content) = edits::split_indentation(minus_line);
+        let (plus_indentation, plus_content) = edits::split_indentation(plus_line);
+        if minus_content != plus_content || minus_indentation == plus_indentation {
+            continue;
+        }
+        diff_style_sections[Minus][minus_index] =
+            vec![(config.indentation_change_style, minus_line.as_str())];
+        diff_style_sections[Plus][plus_index] =
+            vec![(config.indentation_change_style, plus_line.as_str())];
+        hints[plus_index] = indentation_change_hint(minus_indentation, plus_indentation);
+    }
+    if config.indentation_change_hint {
+        Some(hints)
+    } else {
+        None
+    }
+}
+
+/// Return the hint describing the change from `minus_indentation` to `plus_indentation`, if the
+/// width changes. Tabs remain in lines only if they are not expanded (--tabs=0), in which case
+/// their width is unknown, so they are counted separately, e.g. " indentation +4, -1 tab".
+fn indentation_change_hint(minus_indentation: &str, plus_indentation: &str) -> Option<String> {
+    let tabs = |indentation: &str| indentation.matches('\t').count() as isize;
+    let width = |indentation: &str| indentation.split('\t').map(str::width).sum::<usize>() as isize;
+    let width_change = width(plus_indentation) - width(minus_indentation);
+    let tab_change = tabs(plus_indentation) - tabs(minus_indentation);
+    let mut changes = Vec::new();
+    if width_change != 0 {
+        changes.push(format!("{width_change:+}"));
+    }
+    match tab_change {
+        0 => {}
+        1 | -1 => changes.push(format!("{tab_change:+} tab")),
+        _ => changes.push(format!("{tab_change:+} tabs")),
+    }
+    if changes.is_empty() {
+        None
+    } else {
+        Some(format!(" indentation {}", changes.join(", ")))
+    }
+}
+
 pub fn get_syntax_style_sections_for_lines<'a>(
     lines: &'a [(String, State)],
     highlighter: Option<&mut HighlightLines>,
@@ -769,6 +940,8 @@ fn get_diff_style_sections<'a>(
             &config.tokenization_regex,
             config.max_line_distance,
             config.max_line_distance_for_naively_paired_lines,
+            config.line_pairing,
+            config.ignore_indentation,
         );
     let diff_sections = MinusPlus::new(
         minus_line_diff_style_sections,
@@ -830,7 +1003,9 @@ pub fn parse_style_sections<'a>(
 #[allow(clippy::too_many_arguments)]
 pub fn paint_file_path_with_line_number(
     line_number: Option<usize>,
+    column: Option<usize>,
     file_path: &str,
+    commit: Option<&str>,
     pad_line_number: bool,
     separator: &str,
     terminate_with_separator: bool,
@@ -883,6 +1058,8 @@ pub fn paint_file_path_with_line_number(
         Some(absolute_path) => hyperlinks::format_osc8_file_hyperlink(
             absolute_path,
             line_number,
+            column,
+            commit,
             &file_with_line_number,
             config,
         )
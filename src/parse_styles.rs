@@ -232,6 +232,14 @@ fn make_hunk_styles(opt: &cli::Opt, styles: &mut HashMap<&str, StyleReference>)
         opt.git_config(),
     );
 
+    let indentation_change_style = style_from_str(
+        &opt.indentation_change_style,
+        None,
+        None,
+        true_color,
+        opt.git_config(),
+    );
+
     styles.extend([
         ("minus-style", minus_style),
         ("minus-emph-style", minus_emph_style),
@@ -246,6 +254,7 @@ fn make_hunk_styles(opt: &cli::Opt, styles: &mut HashMap<&str, StyleReference>)
         ("plus-non-emph-style", plus_non_emph_style),
         ("plus-empty-line-marker-style", plus_empty_line_marker_style),
         ("whitespace-error-style", whitespace_error_style),
+        ("indentation-change-style", indentation_change_style),
     ])
 }
 
@@ -500,6 +509,36 @@ fn make_merge_conflict_styles(opt: &cli::Opt, styles: &mut HashMap<&str, StyleRe
 }
 
 fn make_misc_styles(opt: &cli::Opt, styles: &mut HashMap<&str, StyleReference>) {
+    styles.insert(
+        "binary-summary-style",
+        style_from_str(
+            &opt.binary_summary_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
+    styles.insert(
+        "collapsed-file-style",
+        style_from_str(
+            &opt.collapsed_file_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
+    styles.insert(
+        "inline-blame-style",
+        style_from_str(
+            &opt.inline_blame_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
     styles.insert(
         "inline-hint-style",
         style_from_str(
@@ -510,6 +549,46 @@ fn make_misc_styles(opt: &cli::Opt, styles: &mut HashMap<&str, StyleReference>)
             opt.git_config(),
         ),
     );
+    styles.insert(
+        "lfs-summary-style",
+        style_from_str(
+            &opt.lfs_summary_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
+    styles.insert(
+        "lockfile-summary-style",
+        style_from_str(
+            &opt.lockfile_summary_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
+    styles.insert(
+        "structural-summary-style",
+        style_from_str(
+            &opt.structural_summary_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
+    styles.insert(
+        "toc-style",
+        style_from_str(
+            &opt.toc_style,
+            None,
+            None,
+            opt.computed.true_color,
+            opt.git_config(),
+        ),
+    );
     styles.insert(
         "git-minus-style",
         StyleReference::Style(
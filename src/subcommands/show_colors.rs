@@ -1,3 +1,5 @@
+use std::io::Write;
+
 use crate::cli;
 use crate::color;
 use crate::colors;
@@ -72,5 +74,5 @@ pub fn show_colors() -> std::io::Result<()> {
             painter.emit()?;
         }
     }
-    Ok(())
+    output_type.finish()
 }
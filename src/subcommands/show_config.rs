@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use crate::cli;
 use crate::config;
+use crate::edits::LinePairing;
 use crate::features::side_by_side::{Left, Right};
 use crate::minusplus::*;
 use crate::paint::BgFillMethod;
@@ -121,6 +122,7 @@ pub fn show_config(config: &config::Config, writer: &mut dyn Write) -> std::io::
     max-line-length               = {max_line_length}
     diff-stat-align-width         = {diff_stat_align_width}
     line-fill-method              = {line_fill_method}
+    line-pairing                  = {line_pairing}
     navigate                      = {navigate}
     navigate-regex                = {navigate_regex}
     pager                         = {pager}
@@ -137,6 +139,10 @@ pub fn show_config(config: &config::Config, writer: &mut dyn Write) -> std::io::
             BgFillMethod::TryAnsiSequence => "ansi",
             BgFillMethod::Spaces => "spaces",
         },
+        line_pairing = match config.line_pairing {
+            LinePairing::Greedy => "greedy",
+            LinePairing::Optimal => "optimal",
+        },
         navigate = config.navigate,
         navigate_regex = match &config.navigate_regex {
             None => "".to_string(),
@@ -49,7 +49,7 @@ pub fn show_syntax_themes() -> std::io::Result<()> {
     } else {
         _show_syntax_themes(opt, Dark, &mut writer, stdin_data.as_ref())?
     };
-    Ok(())
+    output_type.finish()
 }
 
 fn _show_syntax_themes(
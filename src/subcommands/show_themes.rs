@@ -9,15 +9,28 @@ use crate::git_config;
 use crate::options::get::get_themes;
 use crate::utils::bat::output::{OutputType, PagingMode};
 
-pub fn show_themes(dark: bool, light: bool, color_mode: ColorMode) -> std::io::Result<()> {
+pub fn show_themes(
+    env: &DeltaEnv,
+    dark: bool,
+    light: bool,
+    color_mode: ColorMode,
+) -> std::io::Result<()> {
     use std::io::BufReader;
 
     use bytelines::ByteLines;
 
     use super::sample_diff::DIFF;
 
-    let env = DeltaEnv::default();
-    let themes = get_themes(git_config::GitConfig::try_create(&env));
+    // Other environment variables, such as DELTA_FEATURES, would change the themes' appearance.
+    let env = &DeltaEnv {
+        config_home: env.config_home.clone(),
+        delta_config: env.delta_config.clone(),
+        ..DeltaEnv::default()
+    };
+    // Theme files which cannot be read have been reported with the options.
+    let (git_config, _) =
+        git_config::GitConfig::with_config_files(git_config::GitConfig::try_create(env), env);
+    let themes = get_themes(git_config);
     if themes.is_empty() {
         return Err(std::io::Error::new(
             ErrorKind::NotFound,
@@ -35,14 +48,14 @@ pub fn show_themes(dark: bool, light: bool, color_mode: ColorMode) -> std::io::R
         }
     };
 
-    let git_config = git_config::GitConfig::try_create(&env);
+    let git_config = git_config::GitConfig::try_create(env);
     let opt = cli::Opt::from_iter_and_git_config(
-        &env,
+        env,
         &["delta", "--navigate", "--show-themes"],
         git_config,
     );
     let mut output_type = OutputType::from_mode(
-        &env,
+        env,
         PagingMode::Always,
         None,
         &config::Config::from(opt).into(),
@@ -52,18 +65,28 @@ pub fn show_themes(dark: bool, light: bool, color_mode: ColorMode) -> std::io::R
     let writer = output_type.handle().unwrap();
 
     for theme in &themes {
-        let git_config = git_config::GitConfig::try_create(&env);
+        let git_config = git_config::GitConfig::try_create(env);
         let opt =
-            cli::Opt::from_iter_and_git_config(&env, &["delta", "--features", theme], git_config);
+            cli::Opt::from_iter_and_git_config(env, &["delta", "--features", theme], git_config);
         let is_dark_theme = opt.dark;
         let is_light_theme = opt.light;
+        let author = opt
+            .git_config()
+            .and_then(|git_config| git_config.get::<String>(&format!("delta.{theme}.author")));
         let config = config::Config::from(opt);
 
         if (color_mode == ColorMode::Dark && is_dark_theme)
             || (color_mode == ColorMode::Light && is_light_theme)
             || (dark && light)
         {
-            writeln!(writer, "\n\nTheme: {}\n", title_style.paint(theme))?;
+            match author {
+                Some(author) => writeln!(
+                    writer,
+                    "\n\nTheme: {} (by {author})\n",
+                    title_style.paint(theme)
+                )?,
+                None => writeln!(writer, "\n\nTheme: {}\n", title_style.paint(theme))?,
+            }
 
             if let Err(error) =
                 delta::delta(ByteLines::new(BufReader::new(&input[0..])), writer, &config)
@@ -76,5 +99,5 @@ pub fn show_themes(dark: bool, light: bool, color_mode: ColorMode) -> std::io::R
         }
     }
 
-    Ok(())
+    output_type.finish()
 }
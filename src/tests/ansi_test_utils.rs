@@ -192,6 +192,7 @@ pub mod ansi_test_utils {
             &syntax_style_sections,
             &diff_style_sections,
             &[false],
+            None,
             &mut output_buffer,
             config,
             &mut None,
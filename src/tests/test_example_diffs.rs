@@ -2157,6 +2157,742 @@ src/align.rs:71: impl<'a> Alignment<'a> { │
         "###);
     }
 
+    #[test]
+    fn test_hunk_header_breadcrumbs() {
+        // Breadcrumbs are computed from the file, which must exist.
+        let path = std::env::temp_dir().join("delta-test-hunk-header-breadcrumbs.rs");
+        std::fs::write(
+            &path,
+            "impl Painter {\n    fn paint_lines(&self) {\n        let x = 2;\n    }\n}\n",
+        )
+        .unwrap();
+        let input = GIT_DIFF_WITH_CODE_FRAGMENT.replace("PATH", path.to_str().unwrap());
+
+        let result = DeltaTest::with_args(&[
+            "--hunk-header-breadcrumbs",
+            "replace",
+            "--file-style",
+            "omit",
+        ])
+        .with_input(&input);
+        assert_snapshot!(result.output, @r###"
+
+        ─────────────────────────────────┐
+        3: impl Painter > fn paint_lines │
+        ─────────────────────────────────┘
+                let x = 1;
+                let x = 2;
+        "###);
+
+        let result = DeltaTest::with_args(&[
+            "--hunk-header-breadcrumbs",
+            "append",
+            "--file-style",
+            "omit",
+        ])
+        .with_input(&input);
+        assert_snapshot!(result.output, @r###"
+
+        ──────────────────────────────────────────────────┐
+        3: impl Painter { (impl Painter > fn paint_lines) │
+        ──────────────────────────────────────────────────┘
+                let x = 1;
+                let x = 2;
+        "###);
+    }
+
+    #[test]
+    fn test_binary_summary() {
+        // The new version is read from the file, whose blob id is in the "index" line. The old
+        // version's blob cannot be looked up without a repository.
+        let path = std::env::temp_dir().join("delta__test_binary_summary.png");
+        let content =
+            b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x02\x80\x00\x00\x01\xe0\x08\x06";
+        std::fs::write(&path, content).unwrap();
+        let blob_id = git2::Oid::hash_object(git2::ObjectType::Blob, content).unwrap();
+        let input = BINARY_FILES_DIFFER_AT_PATH
+            .replace("PATH", path.to_str().unwrap())
+            .replace("BLOB_ID", &blob_id.to_string()[..7]);
+
+        let result =
+            DeltaTest::with_args(&["--binary-summary", "--file-style", "omit"]).with_input(&input);
+        assert_snapshot!(result.output, @r###"
+        new: image/png 640×480
+        new: 26 bytes
+        "###);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_extra_context() {
+        // Extra context lines are read from the file, which must exist.
+        let path = std::env::temp_dir().join("delta__test_extra_context.txt");
+        std::fs::write(
+            &path,
+            (1..=20).map(|n| format!("line {n}\n")).collect::<String>(),
+        )
+        .unwrap();
+        let input = GIT_DIFF_WITHOUT_CONTEXT.replace("PATH", path.to_str().unwrap());
+
+        let result = DeltaTest::with_args(&["--extra-context", "1", "--file-style", "omit"])
+            .with_input(&input);
+        assert_snapshot!(result.output, @r###"
+
+        ───┐
+        2: │
+        ───┘
+        line 2
+        line three
+        line 3
+        line 4
+
+        ───┐
+        6: │
+        ───┘
+        line 6
+        line seven
+        line 7
+        line 8
+
+        ────┐
+        15: │
+        ────┘
+        line 15
+        line 16
+        line 17
+        "###);
+
+        let result = DeltaTest::with_args(&[
+            "--extra-context",
+            "2",
+            "--file-style",
+            "omit",
+            "--line-numbers",
+        ])
+        .with_input(&input);
+        assert_snapshot!(result.output, @r###"
+
+        ───┐
+        1: │
+        ───┘
+          1 ⋮  1 │line 1
+          2 ⋮  2 │line 2
+          3 ⋮    │line three
+            ⋮  3 │line 3
+          4 ⋮  4 │line 4
+          5 ⋮  5 │line 5
+          6 ⋮  6 │line 6
+          7 ⋮    │line seven
+            ⋮  7 │line 7
+          8 ⋮  8 │line 8
+          9 ⋮  9 │line 9
+
+        ────┐
+        14: │
+        ────┘
+         14 ⋮ 14 │line 14
+         15 ⋮ 15 │line 15
+            ⋮ 16 │line 16
+         16 ⋮ 17 │line 17
+         17 ⋮ 18 │line 18
+        "###);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_semantic_marks() {
+        let result =
+            DeltaTest::with_args(&["--semantic-marks"]).with_input(GIT_LOG_OF_SEVERAL_FILES);
+        let marked_lines: Vec<_> = result
+            .raw_output
+            .lines()
+            .filter(|line| line.starts_with("\x1b]133;A\x1b\\"))
+            .map(strip_ansi_codes)
+            .collect();
+        // 2 commit headers, 4 file headers and 3 hunk headers (boxed, hence the top lines).
+        assert_eq!(
+            marked_lines,
+            vec![
+                "commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e",
+                "src/lib.rs",
+                "───┐",
+                "added: tests/snapshots/one.snap",
+                "───┐",
+                "commit 2f2e9f2b2c3b7d1a5e9c4c2ae2b1a2f1e2d3c4b5",
+                "renamed: src/old.rs ⟶   src/new.rs",
+                "added: src/util.rs",
+                "───┐",
+            ]
+        );
+        let result = DeltaTest::with_args(&[]).with_input(GIT_LOG_OF_SEVERAL_FILES);
+        assert!(!result.raw_output.contains("\x1b]133;"));
+    }
+
+    #[test]
+    fn test_toc() {
+        let result = DeltaTest::with_args(&["--toc", "--collapse-files", "*.json"])
+            .with_input(GIT_DIFF_OF_JSON_AND_YAML_FILES);
+        assert_snapshot!(result.output, @r###"
+          package.json  +2 -2
+          deploy.yaml   +1 -1
+
+        package.json
+        ───────────────────────────────────────────
+
+        ⋯ 4 lines changed (collapsed)
+
+        deploy.yaml
+        ───────────────────────────────────────────
+
+        ──────────┐
+        10: spec: │
+        ──────────┘
+          template:
+            replicas: 2
+            replicas: 3
+          selector: {}
+        "###);
+    }
+
+    #[test]
+    fn test_toc_of_each_commit() {
+        let result = DeltaTest::with_args(&["--toc", "--exclude", "*.snap"])
+            .with_input(GIT_LOG_OF_SEVERAL_FILES);
+        assert_snapshot!(result.output, @r###"
+        commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 10:00:00 2024 -0400
+
+            Add snapshot
+
+          src/lib.rs  +1 -1
+
+        src/lib.rs
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        pub fn one() {}
+        pub fn two() {}
+        commit 2f2e9f2b2c3b7d1a5e9c4c2ae2b1a2f1e2d3c4b5
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 11:00:00 2024 -0400
+
+            Rename module
+
+          renamed: src/old.rs ⟶   src/new.rs  +0 -0
+          added: src/util.rs                  +1 -0
+
+        renamed: src/old.rs ⟶   src/new.rs
+        ───────────────────────────────────────────
+
+        added: src/util.rs
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        pub fn util() {}
+        "###);
+    }
+
+    #[test]
+    fn test_collapse_files() {
+        let result = DeltaTest::with_args(&["--collapse-files", "*.json vendor/**"])
+            .with_input(GIT_DIFF_OF_JSON_AND_YAML_FILES);
+        assert_snapshot!(result.output, @r###"
+
+        package.json
+        ───────────────────────────────────────────
+
+        ⋯ 4 lines changed (collapsed)
+
+        deploy.yaml
+        ───────────────────────────────────────────
+
+        ──────────┐
+        10: spec: │
+        ──────────┘
+          template:
+            replicas: 2
+            replicas: 3
+          selector: {}
+        "###);
+    }
+
+    #[test]
+    fn test_file_filters() {
+        let result =
+            DeltaTest::with_args(&["--exclude", "*.snap"]).with_input(GIT_LOG_OF_SEVERAL_FILES);
+        assert_snapshot!(result.output, @r###"
+        commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 10:00:00 2024 -0400
+
+            Add snapshot
+
+
+        src/lib.rs
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        pub fn one() {}
+        pub fn two() {}
+        commit 2f2e9f2b2c3b7d1a5e9c4c2ae2b1a2f1e2d3c4b5
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 11:00:00 2024 -0400
+
+            Rename module
+
+
+        renamed: src/old.rs ⟶   src/new.rs
+        ───────────────────────────────────────────
+
+        added: src/util.rs
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        pub fn util() {}
+        "###);
+
+        let result = DeltaTest::with_args(&["--include", "src/**", "--only", "added,renamed"])
+            .with_input(GIT_LOG_OF_SEVERAL_FILES);
+        assert_snapshot!(result.output, @r###"
+        commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 10:00:00 2024 -0400
+
+            Add snapshot
+
+        commit 2f2e9f2b2c3b7d1a5e9c4c2ae2b1a2f1e2d3c4b5
+        Author: Dan Davison <dandavison7@gmail.com>
+        Date:   Wed May 15 11:00:00 2024 -0400
+
+            Rename module
+
+
+        renamed: src/old.rs ⟶   src/new.rs
+        ───────────────────────────────────────────
+
+        added: src/util.rs
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        pub fn util() {}
+        "###);
+    }
+
+    #[test]
+    fn test_file_filters_with_diff_unified() {
+        // A removed "-- " comment line does not end the skipped file.
+        let result = DeltaTest::with_args(&["--exclude", "x.sql"])
+            .with_input(DIFF_UNIFIED_WITH_SQL_COMMENTS);
+        assert_snapshot!(result.output, @r###"
+
+        z.sql ⟶   z2.sql
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        select 4;
+        select 5;
+        "###);
+
+        // Files with different names in `diff -u` output are modified, not renamed.
+        let result =
+            DeltaTest::with_args(&["--only", "renamed"]).with_input(DIFF_UNIFIED_WITH_SQL_COMMENTS);
+        assert_snapshot!(result.output, @"");
+        let result = DeltaTest::with_args(&["--only", "modified"])
+            .with_input(DIFF_UNIFIED_WITH_SQL_COMMENTS);
+        assert_snapshot!(result.output, @r###"
+
+        x.sql ⟶   y.sql
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        -- comment
+        select 1;
+        select 2;
+        select 3;
+
+        z.sql ⟶   z2.sql
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        select 4;
+        select 5;
+        "###);
+    }
+
+    #[test]
+    fn test_lfs_pointer_summary() {
+        let result = DeltaTest::with_args(&["--lfs-summary"]).with_input(GIT_DIFF_OF_LFS_POINTERS);
+        assert_snapshot!(result.output, @r###"
+
+        assets/logo.psd
+        ───────────────────────────────────────────
+
+        Git LFS object changed: 1,048,576 → 1,310,720 bytes (sha256:4d7a2146 → 9f86d081)
+
+        added: assets/intro.mp4
+        ───────────────────────────────────────────
+
+        Git LFS object added: 25,165,824 bytes (sha256:2c26b46b)
+
+        notes.txt
+        ───────────────────────────────────────────
+
+        ───┐
+        1: │
+        ───┘
+        version one
+        oid two
+        oid three
+        "###);
+
+        // Pointer files are shown as text by default.
+        DeltaTest::with_args(&[])
+            .with_input(GIT_DIFF_OF_LFS_POINTERS)
+            .expect_contains("version https://git-lfs.github.com/spec/v1")
+            .expect_contains("oid sha256:2c26b46b");
+    }
+
+    #[test]
+    fn test_lockfile_summary() {
+        let result = DeltaTest::with_args(&["--lockfile-summary"])
+            .explain_ansi()
+            .with_input(GIT_DIFF_OF_CARGO_LOCK);
+        assert_snapshot!(result.output, @r###"
+        (normal)
+        (blue)Cargo.lock(normal)
+        (blue)───────────────────────────────────────────(normal)
+        (blue)~ regex  1.7.1 → 1.8.0(normal)
+        (blue)+ serde  1.0.200(normal)
+
+        (blue)─────────────────────(blue)┐(normal)
+        (blue)40(normal):(231) (203)dependencies(231) = [ (blue)│(normal)
+        (blue)─────────────────────(blue)┘(normal)
+
+        (231)[[(149)package(231)]](normal)
+        (203)name(231) = (186)"regex"(normal)
+        (normal 52)version = "1.(normal 124)7(normal 52).(normal 124)1(normal 52)"(normal)
+        (203 22)version(231) = (186)"1.(normal 28)8(normal 22).(normal 28)0(normal 22)"(normal)
+        (203)source(231) = (186)"registry+https://github.com/rust-lang/crates.io-index"(normal)
+        (203)dependencies(231) = [(normal)
+        (231) (186)"aho-corasick"(231),(normal)
+
+        (blue)─────────────────────(blue)┐(normal)
+        (blue)60(normal):(231) (203)dependencies(231) = [ (blue)│(normal)
+        (blue)─────────────────────(blue)┘(normal)
+        (231) (186)"regex"(231),(normal)
+        (231)](normal)
+
+        (231 22)[[(149)package(231)]](normal)
+        (203 22)name(231) = (186)"serde"(normal)
+        (203 22)version(231) = (186)"1.0.200"(normal)
+        (203 22)source(231) = (186)"registry+https://github.com/rust-lang/crates.io-index"(normal)
+        (normal)
+        (231)[[(149)package(231)]](normal)
+        (203)name(231) = (186)"syntect"(normal)
+        (203)version(231) = (186)"5.0.0"(normal)
+        "###);
+
+        let result = DeltaTest::with_args(&["--collapse-lockfile-hunks"])
+            .explain_ansi()
+            .with_input(GIT_DIFF_OF_CARGO_LOCK);
+        assert_snapshot!(result.output, @r###"
+        (normal)
+        (blue)Cargo.lock(normal)
+        (blue)───────────────────────────────────────────(normal)
+        (blue)~ regex  1.7.1 → 1.8.0(normal)
+        (blue)+ serde  1.0.200(normal)
+        "###);
+    }
+
+    #[test]
+    fn test_structural_summary() {
+        // The end of the JSON file is known from its new version, which is read from the file.
+        let path = std::env::temp_dir().join("delta__test_structural_summary.json");
+        let content = r#"{
+  "name": "app",
+  "version": "1.1.0",
+  "dependencies": {
+    "right-pad": "1.0.0"
+  }
+}
+"#;
+        std::fs::write(&path, content).unwrap();
+        let blob_id = git2::Oid::hash_object(git2::ObjectType::Blob, content.as_bytes()).unwrap();
+        let input = GIT_DIFF_OF_JSON_AND_YAML_FILES
+            .replace("package.json", path.to_str().unwrap())
+            .replacen(
+                "index 1..2",
+                &format!("index 1..{}", &blob_id.to_string()[..7]),
+                1,
+            );
+
+        let result = DeltaTest::with_args(&[
+            "--structural-summary",
+            "--structural-summary-style=green",
+            "--file-style",
+            "omit",
+        ])
+        .explain_ansi()
+        .with_input(&input);
+
+        // YAML files are not summarized.
+        assert_snapshot!(result.output, @r###"
+        (green)- dependencies.left-pad(normal)
+        (green)+ dependencies.right-pad(normal)
+        (green)version: "1.0.0" → "1.1.0"(normal)
+
+        (blue)───(blue)┐(normal)
+        (blue)1(normal): (blue)│(normal)
+        (blue)───(blue)┘(normal)
+        (231){(normal)
+        (231)  (208)"name"(231): (186)"app"(231),(normal)
+        (normal 52)  "version": "1.(normal 124)0(normal 52).0",(normal)
+        (231 22)  (208)"version"(231): (186)"1.(normal 28)1(normal 22).0"(231),(normal)
+        (231)  (208)"dependencies"(231): {(normal)
+        (normal 52)    "(normal 124)left(normal 52)-pad": "1.0.0"(normal)
+        (231 22)    (208)"(normal 28)right(normal 22)-pad"(231): (186)"1.0.0"(normal)
+        (231)  }(normal)
+        (231)}(normal)
+
+        (blue)──────────(blue)┐(normal)
+        (blue)10(normal):(231) (203)spec(231): (blue)│(normal)
+        (blue)──────────(blue)┘(normal)
+        (231)  (203)template(231):(normal)
+        (normal 52)    replicas: (normal 124)2(normal)
+        (231 22)    (203)replicas(231): (141 28)3(normal)
+        (231)  (203)selector(231): {}(normal)
+        "###);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_ignore_indentation() {
+        let result = DeltaTest::with_args(&[
+            "--ignore-indentation",
+            "--indentation-change-hint",
+            "--indentation-change-style=yellow",
+        ])
+        .explain_ansi()
+        .with_input(GIT_DIFF_WITH_INDENTATION_CHANGE);
+
+        // The re-indented line is shown in indentation-change-style; in the other pair only the
+        // changed token is emphasized, not the change in indentation.
+        assert_snapshot!(result.output, @r###"
+        (normal)
+        (blue)a.py(normal)
+        (blue)───────────────────────────────────────────(normal)
+
+        (blue)───(blue)┐(normal)
+        (blue)1(normal): (blue)│(normal)
+        (blue)───(blue)┘(normal)
+        (yellow)foo(a)(normal)
+        (normal 52)bar((normal 124)b(normal 52))(normal)
+        (203 22)if(231) x:(normal)
+        (yellow)    foo(a)(blue) indentation +4(normal)
+        (231 22)    bar((normal 28)c(normal 22))(normal)
+        (231)end(normal)
+        "###);
+    }
+
+    #[test]
+    fn test_ignore_indentation_with_tabs() {
+        let input = GIT_DIFF_WITH_INDENTATION_CHANGE
+            .replace("-foo(a)", "-\tfoo(a)")
+            .replace("+    foo(a)", "+\t\tfoo(a)");
+
+        // Tabs are expanded by default, so the hint measures them with the tab width.
+        DeltaTest::with_args(&["--ignore-indentation", "--indentation-change-hint"])
+            .with_input(&input)
+            .expect_contains("                foo(a) indentation +8");
+        // Otherwise, tabs have no width and are counted separately.
+        DeltaTest::with_args(&[
+            "--ignore-indentation",
+            "--indentation-change-hint",
+            "--tabs=0",
+        ])
+        .with_input(&input)
+        .expect_contains("\t\tfoo(a) indentation +1 tab");
+    }
+
+    const GIT_LOG_OF_SEVERAL_FILES: &str = r#"commit 94907c0f136f46dc46ffae2dc92dca9af7eb7c2e
+Author: Dan Davison <dandavison7@gmail.com>
+Date:   Wed May 15 10:00:00 2024 -0400
+
+    Add snapshot
+
+diff --git a/src/lib.rs b/src/lib.rs
+index 8b13789..3b18e51 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-pub fn one() {}
++pub fn two() {}
+diff --git a/tests/snapshots/one.snap b/tests/snapshots/one.snap
+new file mode 100644
+index 0000000..d00491f
+--- /dev/null
++++ b/tests/snapshots/one.snap
+@@ -0,0 +1 @@
++one
+commit 2f2e9f2b2c3b7d1a5e9c4c2ae2b1a2f1e2d3c4b5
+Author: Dan Davison <dandavison7@gmail.com>
+Date:   Wed May 15 11:00:00 2024 -0400
+
+    Rename module
+
+diff --git a/src/old.rs b/src/new.rs
+similarity index 100%
+rename from src/old.rs
+rename to src/new.rs
+diff --git a/src/util.rs b/src/util.rs
+new file mode 100644
+index 0000000..e69de29
+--- /dev/null
++++ b/src/util.rs
+@@ -0,0 +1 @@
++pub fn util() {}
+"#;
+
+    const GIT_DIFF_OF_LFS_POINTERS: &str = r#"diff --git a/assets/logo.psd b/assets/logo.psd
+index 6c3e1d0..a1f9b42 100644
+--- a/assets/logo.psd
++++ b/assets/logo.psd
+@@ -1,3 +1,3 @@
+ version https://git-lfs.github.com/spec/v1
+-oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393
+-size 1048576
++oid sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
++size 1310720
+diff --git a/assets/intro.mp4 b/assets/intro.mp4
+new file mode 100644
+index 0000000..3b18e51
+--- /dev/null
++++ b/assets/intro.mp4
+@@ -0,0 +1,3 @@
++version https://git-lfs.github.com/spec/v1
++oid sha256:2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae
++size 25165824
+diff --git a/notes.txt b/notes.txt
+index 5716ca5..8c7e5a6 100644
+--- a/notes.txt
++++ b/notes.txt
+@@ -1,2 +1,2 @@
+ version one
+-oid two
++oid three
+"#;
+
+    const GIT_DIFF_WITH_CODE_FRAGMENT: &str = r#"--- PATH
++++ PATH
+@@ -3 +3 @@ impl Painter {
+-        let x = 1;
++        let x = 2;
+"#;
+
+    const BINARY_FILES_DIFFER_AT_PATH: &str = "\
+diff --git a/PATH b/PATH
+index 1234567..BLOB_ID 100644
+Binary files a/PATH and b/PATH differ
+";
+
+    const GIT_DIFF_WITHOUT_CONTEXT: &str = r#"--- PATH
++++ PATH
+@@ -3 +3 @@
+-line three
++line 3
+@@ -7 +7 @@
+-line seven
++line 7
+@@ -15,0 +16 @@
++line 16
+"#;
+
+    const GIT_DIFF_OF_CARGO_LOCK: &str = r#"diff --git a/Cargo.lock b/Cargo.lock
+index 1..2 100644
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -40,7 +40,7 @@ dependencies = [
+ 
+ [[package]]
+ name = "regex"
+-version = "1.7.1"
++version = "1.8.0"
+ source = "registry+https://github.com/rust-lang/crates.io-index"
+ dependencies = [
+  "aho-corasick",
+@@ -60,6 +60,11 @@ dependencies = [
+  "regex",
+ ]
+ 
++[[package]]
++name = "serde"
++version = "1.0.200"
++source = "registry+https://github.com/rust-lang/crates.io-index"
++
+ [[package]]
+ name = "syntect"
+ version = "5.0.0"
+"#;
+
+    const GIT_DIFF_OF_JSON_AND_YAML_FILES: &str = r#"diff --git a/package.json b/package.json
+index 1..2 100644
+--- a/package.json
++++ b/package.json
+@@ -1,7 +1,7 @@
+ {
+   "name": "app",
+-  "version": "1.0.0",
++  "version": "1.1.0",
+   "dependencies": {
+-    "left-pad": "1.0.0"
++    "right-pad": "1.0.0"
+   }
+ }
+diff --git a/deploy.yaml b/deploy.yaml
+index 1..2 100644
+--- a/deploy.yaml
++++ b/deploy.yaml
+@@ -10,3 +10,3 @@ spec:
+   template:
+-    replicas: 2
++    replicas: 3
+   selector: {}
+"#;
+
+    const GIT_DIFF_WITH_INDENTATION_CHANGE: &str = "\
+diff --git a/a.py b/a.py
+index 1..2 100644
+--- a/a.py
++++ b/a.py
+@@ -1,3 +1,4 @@
+-foo(a)
+-bar(b)
++if x:
++    foo(a)
++    bar(c)
+ end
+";
+
     const GIT_DIFF_OF_WIDTH_81: &str = r#"
 --- a.rs
 +++ b.rs
@@ -2507,6 +3243,21 @@ Only in b/: just_b
  test.
 ";
 
+    const DIFF_UNIFIED_WITH_SQL_COMMENTS: &str = "\
+--- x.sql	2024-05-15 10:00:00.000000000 -0400
++++ y.sql	2024-05-15 10:00:00.000000000 -0400
+@@ -1,3 +1,2 @@
+--- comment
+ select 1;
+-select 2;
++select 3;
+--- z.sql	2024-05-15 10:00:00.000000000 -0400
++++ z2.sql	2024-05-15 10:00:00.000000000 -0400
+@@ -1 +1 @@
+-select 4;
++select 5;
+";
+
     const NOT_A_DIFF_OUTPUT: &str = "\
 Hello world
 This is a regular file that contains:
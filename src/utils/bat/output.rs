@@ -12,6 +12,7 @@ use crate::config;
 use crate::env::DeltaEnv;
 use crate::fatal;
 use crate::features::navigate;
+use crate::utils::builtin_pager::{BuiltinPager, BUILTIN_PAGER};
 
 #[derive(Debug, Default)]
 pub struct PagerCfg {
@@ -49,14 +50,24 @@ use crate::errors::*;
 
 pub enum OutputType {
     Pager(Child),
+    BuiltinPager(BuiltinPager),
     Stdout(io::Stdout),
     Capture,
 }
 
 impl Drop for OutputType {
     fn drop(&mut self) {
-        if let OutputType::Pager(ref mut command) = *self {
-            let _ = command.wait();
+        match *self {
+            OutputType::Pager(ref mut command) => {
+                let _ = command.wait();
+            }
+            // As for an external pager, wait for the user to quit, if finish() was not called.
+            OutputType::BuiltinPager(ref mut pager) => {
+                if let Err(error) = pager.finish() {
+                    eprintln!("{error}");
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -73,7 +84,16 @@ impl OutputType {
         )
         .unwrap();
         let mut writer = output_type.handle().unwrap();
-        write!(&mut writer, "{data}")
+        write!(&mut writer, "{data}")?;
+        output_type.finish()
+    }
+
+    /// Wait for the user to quit the built-in pager, once all the output has been written to it.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match self {
+            OutputType::BuiltinPager(pager) => pager.finish(),
+            _ => Ok(()),
+        }
     }
 
     pub fn from_mode(
@@ -125,6 +145,9 @@ impl OutputType {
         .context("Could not parse pager command.")?;
 
         Ok(match pager_cmd.split_first() {
+            Some((pager_path, [])) if pager_path == BUILTIN_PAGER => {
+                OutputType::BuiltinPager(BuiltinPager::new(quit_if_one_screen))
+            }
             Some((pager_path, args)) => {
                 let pager_path = PathBuf::from(pager_path);
 
@@ -165,6 +188,7 @@ impl OutputType {
                 .stdin
                 .as_mut()
                 .context("Could not open stdin for pager")?,
+            OutputType::BuiltinPager(ref mut pager) => pager,
             OutputType::Stdout(ref mut handle) => handle,
             OutputType::Capture => unreachable!("capture can not be set"),
         })
@@ -222,7 +246,7 @@ fn _make_process_from_less_path(
         p.env("LESSCHARSET", "UTF-8");
         p.env("LESSANSIENDCHARS", "mK");
 
-        if config.navigate {
+        if config.navigate && config.navigate_regex.is_some() {
             if let Ok(hist_file) = navigate::copy_less_hist_file_and_append_navigate_regex(config) {
                 p.env("LESSHISTFILE", hist_file);
                 if config.show_themes {
@@ -0,0 +1,809 @@
+//! A pager built into delta (--pager=builtin). Unlike an external pager it knows where the
+//! commits, files and hunks of delta's output start, from invisible markers written before their
+//! headers, so that it can jump between them and collapse files.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use console::{Key, Term};
+use regex::Regex;
+
+use crate::ansi;
+use crate::features::navigate::NavigateLevel;
+
+/// The value of --pager or DELTA_PAGER selecting the built-in pager.
+pub const BUILTIN_PAGER: &str = "builtin";
+
+// Markers are APC sequences, which terminals ignore.
+const MARKER_PREFIX: &str = "\x1b_delta:";
+const MARKER_SUFFIX: &str = "\x1b\\";
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLOSE_HYPERLINK: &str = "\x1b]8;;\x1b\\";
+const END_REVERSE: &str = "\x1b[27m";
+
+const HELP: &str = "q:quit  ]/[:file  }/{:hunk  )/(:commit  c:collapse  /:search  n/N:match";
+
+/// Return the marker written before the header of a commit, file or hunk, from which the built-in
+/// pager knows where they start.
+pub fn format_section_marker(level: NavigateLevel) -> String {
+    format!("{MARKER_PREFIX}{}{MARKER_SUFFIX}", section_name(level))
+}
+
+pub fn section_name(level: NavigateLevel) -> &'static str {
+    match level {
+        NavigateLevel::Commit => "commit",
+        NavigateLevel::File => "file",
+        NavigateLevel::Hunk => "hunk",
+        NavigateLevel::Conflict => "conflict",
+    }
+}
+
+pub fn parse_section_name(name: &str) -> Option<NavigateLevel> {
+    match name {
+        "commit" => Some(NavigateLevel::Commit),
+        "file" => Some(NavigateLevel::File),
+        "hunk" => Some(NavigateLevel::Hunk),
+        "conflict" => Some(NavigateLevel::Conflict),
+        _ => None,
+    }
+}
+
+/// Remove the markers from a line of output, and return the line and the section whose header
+/// starts on it, if any.
+pub fn take_section_marker(line: &str) -> (String, Option<NavigateLevel>) {
+    let mut line = line.to_string();
+    let mut section = None;
+    while let Some(start) = line.find(MARKER_PREFIX) {
+        let Some(length) = line[start..].find(MARKER_SUFFIX) else {
+            break;
+        };
+        let name = &line[start + MARKER_PREFIX.len()..start + length];
+        section = section.or_else(|| parse_section_name(name));
+        line.replace_range(start..start + length + MARKER_SUFFIX.len(), "");
+    }
+    (line, section)
+}
+
+/// Output is sent to the display in chunks of at least this many bytes, or written in this
+/// interval.
+const CHUNK_SIZE: usize = 1 << 16;
+const SEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The number of chunks which may be written ahead of the display. Once they have been written,
+/// delta waits for the display to read more output, as it would for an external pager.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// The output written to the built-in pager. If it is going to a terminal, it is sent to a thread
+/// which displays it interactively, reading it as far as is needed to display the current screen.
+/// Otherwise it is written to stdout, without the section markers.
+pub struct BuiltinPager {
+    pending: Vec<u8>,
+    display: Display,
+}
+
+enum Display {
+    Terminal {
+        sender: Option<SyncSender<Vec<u8>>>,
+        thread: Option<JoinHandle<io::Result<()>>>,
+        last_sent: Instant,
+    },
+    Stdout,
+}
+
+impl BuiltinPager {
+    /// Start the display of the output, if it is going to a terminal. If `quit_if_one_screen`
+    /// (--paging=auto), output which fits on one screen is written to it non-interactively.
+    pub fn new(quit_if_one_screen: bool) -> Self {
+        let display = if Term::stdout().is_term() {
+            let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+            Display::Terminal {
+                sender: Some(sender),
+                thread: Some(thread::spawn(move || {
+                    display_interactively(receiver, quit_if_one_screen)
+                })),
+                last_sent: Instant::now(),
+            }
+        } else {
+            Display::Stdout
+        };
+        Self {
+            pending: Vec::new(),
+            display,
+        }
+    }
+
+    /// Send the rest of the output to the display, and wait for the user to quit it.
+    pub fn finish(&mut self) -> io::Result<()> {
+        let Self { pending, display } = self;
+        match display {
+            Display::Terminal { sender, thread, .. } => {
+                if let Some(sender) = sender.take() {
+                    // The send fails if the user has quit the display, which is not an error.
+                    sender.send(std::mem::take(pending)).ok();
+                }
+                match thread.take() {
+                    Some(thread) => thread
+                        .join()
+                        .unwrap_or_else(|_| Err(io::Error::other("The built-in pager panicked"))),
+                    None => Ok(()),
+                }
+            }
+            Display::Stdout if pending.is_empty() => Ok(()),
+            Display::Stdout => {
+                pending.push(b'\n');
+                write_without_markers(&std::mem::take(pending))
+            }
+        }
+    }
+}
+
+impl Write for BuiltinPager {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Self { pending, display } = self;
+        pending.extend_from_slice(buf);
+        match display {
+            Display::Terminal {
+                sender: Some(sender),
+                last_sent,
+                ..
+            } => {
+                if pending.len() >= CHUNK_SIZE || last_sent.elapsed() >= SEND_INTERVAL {
+                    // The display has been quit: stop, as when an external pager exits.
+                    sender
+                        .send(std::mem::take(pending))
+                        .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+                    *last_sent = Instant::now();
+                }
+            }
+            Display::Terminal { sender: None, .. } => {
+                return Err(io::Error::from(io::ErrorKind::BrokenPipe));
+            }
+            Display::Stdout => {
+                if let Some(end) = pending.iter().rposition(|&byte| byte == b'\n') {
+                    let lines: Vec<u8> = pending.drain(..=end).collect();
+                    write_without_markers(&lines)?;
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write complete lines of output to stdout, without their section markers.
+fn write_without_markers(lines: &[u8]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    let lines = String::from_utf8_lossy(lines);
+    for line in lines.strip_suffix('\n').unwrap_or(&lines).split('\n') {
+        writeln!(stdout, "{}", take_section_marker(line).0)?;
+    }
+    stdout.flush()
+}
+
+/// Display the output received from delta, until the user quits.
+fn display_interactively(receiver: Receiver<Vec<u8>>, quit_if_one_screen: bool) -> io::Result<()> {
+    let term = Term::stdout();
+    let mut pager = Pager::new(Document::default(), Some(receiver));
+    if quit_if_one_screen {
+        let (height, width) = term.size();
+        pager.load_rows(height as usize);
+        if pager.source.is_none() && pager.document.fits(height as usize, width as usize) {
+            return pager.document.write_lines();
+        }
+    }
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "{ENTER_ALTERNATE_SCREEN}")?;
+    // Keys are read in raw mode by console. One typed while the screen is being updated may be
+    // echoed on the status line, which is cleared when the screen is next rendered.
+    term.hide_cursor()?;
+    let result = pager.interact(&term, &mut stdout);
+    term.show_cursor()?;
+    write!(stdout, "{LEAVE_ALTERNATE_SCREEN}")?;
+    stdout.flush()?;
+    result
+}
+
+#[derive(Debug)]
+struct Line {
+    text: String,
+    plain: String,
+    section: Option<NavigateLevel>,
+}
+
+/// The lines of the output, and where its sections start.
+#[derive(Debug, Default)]
+struct Document {
+    lines: Vec<Line>,
+    /// For each line, the index of the first line of the file it belongs to, if any.
+    files: Vec<Option<usize>>,
+}
+
+impl Document {
+    fn push_line(&mut self, line: &str) {
+        let (text, section) = take_section_marker(line);
+        let file = match section {
+            Some(NavigateLevel::Commit) => None,
+            Some(NavigateLevel::File) => Some(self.lines.len()),
+            _ => self.files.last().copied().flatten(),
+        };
+        self.files.push(file);
+        self.lines.push(Line {
+            plain: ansi::strip_ansi_codes(&text),
+            text,
+            section,
+        });
+    }
+
+    fn fits(&self, height: usize, width: usize) -> bool {
+        self.lines.len() < height
+            && self
+                .lines
+                .iter()
+                .all(|line| ansi::measure_text_width(&line.text) <= width)
+    }
+
+    fn write_lines(&self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        for line in &self.lines {
+            writeln!(stdout, "{}", line.text)?;
+        }
+        stdout.flush()
+    }
+
+    /// Return the rows to display: the lines, except those of collapsed files, which are replaced
+    /// by a single row. The header of a collapsed file is kept, up to its first line with text
+    /// (a decoration may precede the file name).
+    fn rows(&self, collapsed_files: &HashSet<usize>) -> Vec<Row> {
+        let n_lines = self.lines.len();
+        let mut rows = Vec::new();
+        let mut i = 0;
+        while i < n_lines {
+            rows.push(Row::Line(i));
+            if !collapsed_files.contains(&i) {
+                i += 1;
+                continue;
+            }
+            let mut header_end = i;
+            while header_end + 1 < n_lines
+                && !has_text(&self.lines[header_end].plain)
+                && self.files[header_end + 1] == Some(i)
+            {
+                header_end += 1;
+                rows.push(Row::Line(header_end));
+            }
+            let end = (header_end + 1..n_lines)
+                .find(|&j| self.files[j] != Some(i))
+                .unwrap_or(n_lines);
+            if end > header_end + 1 {
+                rows.push(Row::Collapsed(end - header_end - 1));
+            }
+            i = end;
+        }
+        rows
+    }
+}
+
+fn has_text(s: &str) -> bool {
+    s.chars().any(char::is_alphanumeric)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Row {
+    Line(usize),
+    /// The given number of hidden lines of a collapsed file.
+    Collapsed(usize),
+}
+
+/// The state of the interactive display.
+struct Pager {
+    document: Document,
+    /// The output yet to be read, until all of it has been.
+    source: Option<Receiver<Vec<u8>>>,
+    /// The start of a line of output whose end has not been read yet.
+    partial_line: Vec<u8>,
+    collapsed_files: HashSet<usize>,
+    rows: Vec<Row>,
+    top: usize,
+    search: Option<Regex>,
+    message: Option<String>,
+}
+
+impl Pager {
+    fn new(document: Document, source: Option<Receiver<Vec<u8>>>) -> Self {
+        Self {
+            rows: document.rows(&HashSet::new()),
+            document,
+            source,
+            partial_line: Vec::new(),
+            collapsed_files: HashSet::new(),
+            top: 0,
+            search: None,
+            message: None,
+        }
+    }
+
+    /// Read the next chunk of output, waiting for delta to write it. Return false if all of it has
+    /// been read.
+    fn read_more(&mut self) -> bool {
+        let Some(source) = &self.source else {
+            return false;
+        };
+        match source.recv() {
+            Ok(chunk) => {
+                self.partial_line.extend_from_slice(&chunk);
+                if let Some(end) = self.partial_line.iter().rposition(|&byte| byte == b'\n') {
+                    let lines: Vec<u8> = self.partial_line.drain(..=end).collect();
+                    for line in String::from_utf8_lossy(&lines[..end]).split('\n') {
+                        self.document.push_line(line);
+                    }
+                }
+            }
+            Err(_) => {
+                self.source = None;
+                if !self.partial_line.is_empty() {
+                    let line = std::mem::take(&mut self.partial_line);
+                    self.document.push_line(&String::from_utf8_lossy(&line));
+                }
+            }
+        }
+        self.rows = self.document.rows(&self.collapsed_files);
+        true
+    }
+
+    /// Read output until there are at least `n_rows` rows, or all of it has been read.
+    fn load_rows(&mut self, n_rows: usize) {
+        while self.rows.len() < n_rows && self.read_more() {}
+    }
+
+    /// Return the index of the next (or previous) row after (or before) the top one which
+    /// satisfies `is_match`, reading output until one is found.
+    fn find_row(
+        &mut self,
+        forward: bool,
+        is_match: impl Fn(&Document, Row) -> bool,
+    ) -> Option<usize> {
+        if !forward {
+            return (0..self.top)
+                .rev()
+                .find(|&i| is_match(&self.document, self.rows[i]));
+        }
+        let mut start = self.top + 1;
+        loop {
+            if let Some(i) =
+                (start..self.rows.len()).find(|&i| is_match(&self.document, self.rows[i]))
+            {
+                return Some(i);
+            }
+            // The last row may be a collapsed file, which grows.
+            start = start.max(self.rows.len().saturating_sub(1));
+            if !self.read_more() {
+                return None;
+            }
+        }
+    }
+
+    fn interact(&mut self, term: &Term, writer: &mut dyn Write) -> io::Result<()> {
+        loop {
+            let (height, width) = term.size();
+            let page_height = (height as usize).saturating_sub(1).max(1);
+            self.load_rows(self.top + page_height);
+            let status = self.message.take().unwrap_or_else(|| self.status());
+            self.render(writer, page_height, width as usize, &status)?;
+            // An error reading a key, e.g. Ctrl-C, ends the display as q does.
+            let Ok(key) = term.read_key() else {
+                return Ok(());
+            };
+            match key {
+                Key::Char('q' | 'Q') | Key::Escape => return Ok(()),
+                Key::Char('j' | 'e') | Key::ArrowDown | Key::Enter => {
+                    self.scroll_by(1, page_height)
+                }
+                Key::Char('k' | 'y') | Key::ArrowUp => self.scroll_by(-1, page_height),
+                Key::Char(' ' | 'f') | Key::PageDown => {
+                    self.scroll_by(page_height as isize, page_height)
+                }
+                Key::Char('b') | Key::PageUp => {
+                    self.scroll_by(-(page_height as isize), page_height)
+                }
+                Key::Char('d') => self.scroll_by(page_height as isize / 2, page_height),
+                Key::Char('u') => self.scroll_by(-(page_height as isize) / 2, page_height),
+                Key::Char('g' | '<') | Key::Home => self.top = 0,
+                Key::Char('G' | '>') | Key::End => self.scroll_by(isize::MAX, page_height),
+                Key::Char(']') => self.jump(NavigateLevel::File, true),
+                Key::Char('[') => self.jump(NavigateLevel::File, false),
+                Key::Char('}') => self.jump(NavigateLevel::Hunk, true),
+                Key::Char('{') => self.jump(NavigateLevel::Hunk, false),
+                Key::Char(')') => self.jump(NavigateLevel::Commit, true),
+                Key::Char('(') => self.jump(NavigateLevel::Commit, false),
+                Key::Char('c') => self.toggle_collapsed_file(),
+                Key::Char('/') => {
+                    if let Some(pattern) = self.read_pattern(term, writer, page_height, width)? {
+                        self.set_search(&pattern);
+                    }
+                }
+                Key::Char('n') => self.search_next(true),
+                Key::Char('N') => self.search_next(false),
+                _ => {}
+            }
+        }
+    }
+
+    fn render(
+        &self,
+        writer: &mut dyn Write,
+        page_height: usize,
+        width: usize,
+        status: &str,
+    ) -> io::Result<()> {
+        write!(writer, "{CURSOR_HOME}")?;
+        for i in self.top..self.top + page_height {
+            if let Some(row) = self.rows.get(i) {
+                let text = self.format_row(*row);
+                write!(writer, "{}", ansi::truncate_str(&text, width, "→"))?;
+            }
+            write!(
+                writer,
+                "{}{CLOSE_HYPERLINK}{}\r\n",
+                ansi::ANSI_SGR_RESET,
+                ansi::ANSI_CSI_CLEAR_TO_EOL
+            )?;
+        }
+        write!(
+            writer,
+            "{}{}{}{}",
+            ansi::ANSI_SGR_REVERSE,
+            ansi::truncate_str(status, width, "→"),
+            ansi::ANSI_SGR_RESET,
+            ansi::ANSI_CSI_CLEAR_TO_EOL
+        )?;
+        writer.flush()
+    }
+
+    fn format_row(&self, row: Row) -> String {
+        match row {
+            Row::Line(i) => {
+                let line = &self.document.lines[i];
+                match &self.search {
+                    Some(regex) => highlight_matches(&line.text, &line.plain, regex),
+                    None => line.text.clone(),
+                }
+            }
+            Row::Collapsed(n) => format!(
+                "⋯ {n} {} (collapsed)",
+                if n == 1 { "line" } else { "lines" }
+            ),
+        }
+    }
+
+    /// The position of the top row in the output, unknown until all of it has been read.
+    fn status(&self) -> String {
+        if self.source.is_some() {
+            return format!("   …  {HELP}");
+        }
+        let percent = if self.rows.is_empty() {
+            100
+        } else {
+            (self.top + 1) * 100 / self.rows.len()
+        };
+        format!("{percent:>3}%  {HELP}")
+    }
+
+    fn scroll_by(&mut self, n_rows: isize, page_height: usize) {
+        let top = self.top.saturating_add_signed(n_rows);
+        self.load_rows(top.saturating_add(page_height));
+        let max_top = self.rows.len().saturating_sub(page_height);
+        self.top = top.min(max_top);
+    }
+
+    /// The index of the line displayed at the top, or of the last line before it if the top row
+    /// is the hidden part of a collapsed file.
+    fn top_line(&self) -> Option<usize> {
+        self.rows[..=self.top.min(self.rows.len().checked_sub(1)?)]
+            .iter()
+            .rev()
+            .find_map(|row| match row {
+                Row::Line(i) => Some(*i),
+                Row::Collapsed(_) => None,
+            })
+    }
+
+    /// Move the start of the next (or previous) commit, file or hunk to the top.
+    fn jump(&mut self, level: NavigateLevel, forward: bool) {
+        let is_start = |document: &Document, row: Row| match row {
+            Row::Line(i) => document.lines[i].section == Some(level),
+            Row::Collapsed(_) => false,
+        };
+        match self.find_row(forward, is_start) {
+            Some(i) => self.top = i,
+            None => {
+                let direction = if forward { "next" } else { "previous" };
+                self.message = Some(format!("No {direction} {}", section_name(level)));
+            }
+        }
+    }
+
+    /// Collapse the file displayed at the top, or expand it if it is collapsed.
+    fn toggle_collapsed_file(&mut self) {
+        let Some(file) = self.top_line().and_then(|i| self.document.files[i]) else {
+            self.message = Some("Not in a file".to_string());
+            return;
+        };
+        if !self.collapsed_files.remove(&file) {
+            self.collapsed_files.insert(file);
+        }
+        self.rows = self.document.rows(&self.collapsed_files);
+        self.top = self
+            .rows
+            .iter()
+            .position(|row| *row == Row::Line(file))
+            .unwrap_or(0);
+    }
+
+    /// Read a search pattern on the status line. Return None if the search is cancelled.
+    fn read_pattern(
+        &self,
+        term: &Term,
+        writer: &mut dyn Write,
+        page_height: usize,
+        width: u16,
+    ) -> io::Result<Option<String>> {
+        let mut pattern = String::new();
+        loop {
+            self.render(writer, page_height, width as usize, &format!("/{pattern}"))?;
+            match term.read_key() {
+                Ok(Key::Enter) => return Ok(Some(pattern)),
+                Ok(Key::Backspace) => {
+                    pattern.pop();
+                }
+                Ok(Key::Char(c)) if !c.is_control() => pattern.push(c),
+                Ok(Key::Escape) | Err(_) => return Ok(None),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    /// Search for the pattern, a regular expression which is case-insensitive unless it contains
+    /// an upper case letter, from the line after the top one.
+    fn set_search(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            self.search = None;
+            return;
+        }
+        let case_insensitive = !pattern.chars().any(char::is_uppercase);
+        match regex::RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+        {
+            Ok(regex) => {
+                self.search = Some(regex);
+                self.search_next(true);
+            }
+            Err(_) => self.message = Some(format!("Invalid pattern: {pattern}")),
+        }
+    }
+
+    /// Move the next (or previous) row matching the search to the top.
+    fn search_next(&mut self, forward: bool) {
+        let Some(regex) = self.search.clone() else {
+            self.message = Some("No previous search".to_string());
+            return;
+        };
+        let is_match = |document: &Document, row: Row| match row {
+            Row::Line(i) => regex.is_match(&document.lines[i].plain),
+            Row::Collapsed(_) => false,
+        };
+        match self.find_row(forward, is_match) {
+            Some(i) => self.top = i,
+            None => self.message = Some("Pattern not found".to_string()),
+        }
+    }
+}
+
+/// Return `text` with the matches of `regex` in its text without ANSI escape sequences, `plain`,
+/// displayed in reverse video.
+fn highlight_matches(text: &str, plain: &str, regex: &Regex) -> String {
+    let matches: Vec<_> = regex
+        .find_iter(plain)
+        .filter(|m| !m.is_empty())
+        .map(|m| m.range())
+        .collect();
+    if matches.is_empty() {
+        return text.to_string();
+    }
+    let is_in_match = |offset: usize| matches.iter().any(|range| range.contains(&offset));
+    let mut highlighted = String::new();
+    let mut offset = 0;
+    let mut in_match = false;
+    for (element, is_ansi) in ansi::ansi_strings_iterator(text) {
+        if is_ansi {
+            highlighted.push_str(element);
+            // The sequence may have ended the reverse video.
+            if in_match {
+                highlighted.push_str(ansi::ANSI_SGR_REVERSE);
+            }
+            continue;
+        }
+        for c in element.chars() {
+            match (in_match, is_in_match(offset)) {
+                (false, true) => highlighted.push_str(ansi::ANSI_SGR_REVERSE),
+                (true, false) => highlighted.push_str(END_REVERSE),
+                _ => {}
+            }
+            in_match = is_in_match(offset);
+            highlighted.push(c);
+            offset += c.len_utf8();
+        }
+    }
+    if in_match {
+        highlighted.push_str(END_REVERSE);
+    }
+    highlighted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(lines: &[(Option<NavigateLevel>, &str)]) -> String {
+        lines
+            .iter()
+            .map(|(section, line)| {
+                let marker = section.map(format_section_marker).unwrap_or_default();
+                format!("{marker}{line}\n")
+            })
+            .collect()
+    }
+
+    fn document(lines: &[(Option<NavigateLevel>, &str)]) -> Document {
+        let mut document = Document::default();
+        for line in output(lines).lines() {
+            document.push_line(line);
+        }
+        document
+    }
+
+    fn example_lines() -> Vec<(Option<NavigateLevel>, &'static str)> {
+        use NavigateLevel::*;
+        vec![
+            (Some(Commit), "commit 94907c0f"),
+            (None, ""),
+            (Some(File), "src/lib.rs"),
+            (None, "──────────"),
+            (Some(Hunk), "1: fn one"),
+            (None, "fn one() {}"),
+            (None, "fn two() {}"),
+            (None, ""),
+            (Some(File), "src/new.rs"),
+            (None, "──────────"),
+            (Some(Hunk), "1:"),
+            (None, "fn new() {}"),
+        ]
+    }
+
+    fn example_document() -> Document {
+        document(&example_lines())
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let document = example_document();
+        assert_eq!(document.lines.len(), 12);
+        assert_eq!(document.lines[0].text, "commit 94907c0f");
+        assert_eq!(document.lines[2].section, Some(NavigateLevel::File));
+        assert_eq!(document.lines[3].section, None);
+        assert_eq!(
+            document.files,
+            vec![
+                None,
+                None,
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(8),
+                Some(8),
+                Some(8),
+                Some(8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jump_and_collapse() {
+        let mut pager = Pager::new(example_document(), None);
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.top, 2);
+        pager.jump(NavigateLevel::Hunk, true);
+        assert_eq!(pager.top, 4);
+        pager.toggle_collapsed_file();
+        assert_eq!(pager.top, 2);
+        assert_eq!(
+            &pager.rows[2..5],
+            &[Row::Line(2), Row::Collapsed(5), Row::Line(8)]
+        );
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.top, 4);
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.message.as_deref(), Some("No next file"));
+        pager.jump(NavigateLevel::Commit, false);
+        assert_eq!(pager.top, 0);
+    }
+
+    #[test]
+    fn test_output_is_read_as_needed() {
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let output = output(&example_lines());
+        // Chunks need not end at the end of a line.
+        let (first, rest) = output.split_at(output.find("fn one").unwrap() + 2);
+        sender.send(first.as_bytes().to_vec()).unwrap();
+        sender.send(rest.as_bytes().to_vec()).unwrap();
+        sender.send(b"no newline".to_vec()).unwrap();
+        drop(sender);
+
+        let mut pager = Pager::new(Document::default(), Some(receiver));
+        pager.load_rows(3);
+        assert_eq!(pager.document.lines.len(), 4);
+        assert_eq!(pager.status(), format!("   …  {HELP}"));
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.top, 2);
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.top, 8);
+        assert_eq!(pager.document.lines.len(), 12);
+        pager.jump(NavigateLevel::File, true);
+        assert_eq!(pager.message.as_deref(), Some("No next file"));
+        assert_eq!(pager.document.lines[12].text, "no newline");
+        assert!(pager.source.is_none());
+    }
+
+    #[test]
+    fn test_collapsed_file_keeps_boxed_header() {
+        use NavigateLevel::*;
+        let document = document(&[
+            (Some(File), "───────────┐"),
+            (None, "src/lib.rs │"),
+            (None, "───────────┘"),
+            (None, "fn one() {}"),
+        ]);
+        assert_eq!(
+            document.rows(&HashSet::from([0])),
+            vec![Row::Line(0), Row::Line(1), Row::Collapsed(2)]
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let mut pager = Pager::new(example_document(), None);
+        pager.set_search("fn");
+        assert_eq!(pager.top, 4);
+        pager.search_next(true);
+        assert_eq!(pager.top, 5);
+        pager.set_search("new");
+        assert_eq!(pager.top, 8);
+        // A pattern with an upper case letter is case-sensitive.
+        pager.set_search("New");
+        assert_eq!(pager.message.as_deref(), Some("Pattern not found"));
+        pager.set_search("[");
+        assert_eq!(pager.message.as_deref(), Some("Invalid pattern: ["));
+    }
+
+    #[test]
+    fn test_highlight_matches() {
+        let regex = Regex::new("two").unwrap();
+        let text = "\x1b[31mfn t\x1b[0mwo() {}";
+        assert_eq!(
+            highlight_matches(text, &ansi::strip_ansi_codes(text), &regex),
+            "\x1b[31mfn \x1b[7mt\x1b[0m\x1b[7mwo\x1b[27m() {}"
+        );
+    }
+}
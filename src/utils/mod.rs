@@ -1,10 +1,12 @@
 #[cfg(not(tarpaulin_include))]
 pub mod bat;
+pub mod builtin_pager;
 pub mod git;
 pub mod helpwrap;
 pub mod path;
 pub mod process;
 pub mod regex_replacement;
+pub mod rendered_output;
 pub mod round_char_boundary;
 pub mod syntect;
 pub mod tabs;
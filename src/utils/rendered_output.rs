@@ -0,0 +1,233 @@
+//! Output written to a file (--output), with where its commits, files and hunks start
+//! (--output-sections), and displayed later (--replay).
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::utils::builtin_pager;
+
+/// The start of a commit, file or hunk in a file of rendered output.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Section {
+    section: String,
+    /// The line of its header, starting from 1.
+    line: usize,
+    /// The byte offset of that line.
+    offset: usize,
+}
+
+/// Return the path of the file listing the sections of the rendered output in `path`.
+fn sections_path(path: &Path) -> PathBuf {
+    let mut sections_path = path.as_os_str().to_owned();
+    sections_path.push(".sections.json");
+    PathBuf::from(sections_path)
+}
+
+/// Writes the output to a file, removing the markers of the starts of sections and recording
+/// where they are. The sections are written by `finish()`.
+pub struct RenderedOutputWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+    sections_path: Option<PathBuf>,
+    sections: Vec<Section>,
+    pending_line: Vec<u8>,
+    n_lines: usize,
+    offset: usize,
+    finished: bool,
+}
+
+impl RenderedOutputWriter {
+    pub fn create(path: &Path, with_sections: bool) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: BufWriter::new(File::create(path)?),
+            sections_path: with_sections.then(|| sections_path(path)),
+            sections: Vec::new(),
+            pending_line: Vec::new(),
+            n_lines: 0,
+            offset: 0,
+            finished: false,
+        })
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        let text = String::from_utf8_lossy(line);
+        let (text, section) = builtin_pager::take_section_marker(&text);
+        let line = match section {
+            Some(level) => {
+                self.sections.push(Section {
+                    section: builtin_pager::section_name(level).to_string(),
+                    line: self.n_lines + 1,
+                    offset: self.offset,
+                });
+                text.as_bytes()
+            }
+            None => line,
+        };
+        self.file.write_all(line)?;
+        self.offset += line.len();
+        if line.ends_with(b"\n") {
+            self.n_lines += 1;
+        }
+        Ok(())
+    }
+
+    /// Write any incomplete last line, and the sections, once all the output has been written.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        self.write_remaining_output().map_err(|error| {
+            io::Error::new(
+                error.kind(),
+                format!(
+                    "Failed to write the output to {}: {error}",
+                    self.path.display()
+                ),
+            )
+        })
+    }
+
+    fn write_remaining_output(&mut self) -> io::Result<()> {
+        let pending_line = std::mem::take(&mut self.pending_line);
+        self.write_line(&pending_line)?;
+        self.file.flush()?;
+        if let Some(sections_path) = &self.sections_path {
+            let sections = serde_json::to_string_pretty(&self.sections)?;
+            fs::write(sections_path, sections + "\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for RenderedOutputWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending_line.extend_from_slice(buf);
+        while let Some(i) = self.pending_line.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.pending_line.drain(..=i).collect();
+            self.write_line(&line)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for RenderedOutputWriter {
+    fn drop(&mut self) {
+        // Keep what was written if finish() was not called, e.g. on an early return.
+        if let Err(error) = self.finish() {
+            eprintln!("{error}");
+        }
+    }
+}
+
+/// Write the rendered output in `path`, restoring the markers of the starts of sections for the
+/// built-in pager if they were written.
+pub fn replay(path: &Path, writer: &mut dyn Write, config: &Config) -> io::Result<()> {
+    let output = fs::read(path)?;
+    let sections: HashMap<usize, String> = match fs::read_to_string(sections_path(path)) {
+        Ok(sections) if config.section_markers => serde_json::from_str::<Vec<Section>>(&sections)?
+            .into_iter()
+            .map(|section| (section.line, section.section))
+            .collect(),
+        _ => HashMap::new(),
+    };
+    for (i, line) in output.split_inclusive(|&byte| byte == b'\n').enumerate() {
+        if let Some(level) = sections
+            .get(&(i + 1))
+            .and_then(|name| builtin_pager::parse_section_name(name))
+        {
+            write!(writer, "{}", builtin_pager::format_section_marker(level))?;
+        }
+        writer.write_all(line)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::navigate::NavigateLevel;
+    use crate::tests::integration_test_utils::make_config_from_args;
+
+    #[test]
+    fn test_write_and_replay_rendered_output() {
+        let path = std::env::temp_dir().join("delta__test_write_and_replay_rendered_output.ansi");
+        let file_marker = builtin_pager::format_section_marker(NavigateLevel::File);
+        let hunk_marker = builtin_pager::format_section_marker(NavigateLevel::Hunk);
+        let mut writer = RenderedOutputWriter::create(&path, true).unwrap();
+        write!(writer, "\n{file_marker}\x1b[34msrc/lib.rs\x1b[0m\n").unwrap();
+        write!(writer, "{hunk_marker}1: ").unwrap();
+        write!(writer, "fn one\nfn one() {{}}\n").unwrap();
+        writer.finish().unwrap();
+        let output = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            output,
+            "\n\x1b[34msrc/lib.rs\x1b[0m\n1: fn one\nfn one() {}\n"
+        );
+        let sections: Vec<Section> =
+            serde_json::from_str(&fs::read_to_string(sections_path(&path)).unwrap()).unwrap();
+        assert_eq!(
+            sections,
+            vec![
+                Section {
+                    section: "file".to_string(),
+                    line: 2,
+                    offset: 1,
+                },
+                Section {
+                    section: "hunk".to_string(),
+                    line: 3,
+                    offset: 21,
+                },
+            ]
+        );
+
+        let mut config = make_config_from_args(&[]);
+        let mut replayed = Vec::new();
+        replay(&path, &mut replayed, &config).unwrap();
+        assert_eq!(String::from_utf8(replayed).unwrap(), output);
+
+        config.section_markers = true;
+        let mut replayed = Vec::new();
+        replay(&path, &mut replayed, &config).unwrap();
+        assert_eq!(
+            String::from_utf8(replayed).unwrap(),
+            format!(
+                "\n{file_marker}\x1b[34msrc/lib.rs\x1b[0m\n{hunk_marker}1: fn one\nfn one() {{}}\n"
+            )
+        );
+        fs::remove_file(sections_path(&path)).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_finish_reports_failure_to_write_sections() {
+        let path = std::env::temp_dir().join("delta__test_finish_reports_failure.ansi");
+        // The sections cannot be written to a directory.
+        fs::create_dir_all(sections_path(&path)).unwrap();
+        let mut writer = RenderedOutputWriter::create(&path, true).unwrap();
+        writer.write_all(b"output\n").unwrap();
+        let error = writer.finish().unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with(&format!("Failed to write the output to {}", path.display())));
+        fs::remove_dir(sections_path(&path)).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Options --output and --replay cannot be used together.")]
+    fn test_output_and_replay_cannot_be_used_together() {
+        make_config_from_args(&["--output", "output.ansi", "--replay", "output.ansi"]);
+    }
+}